@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+
+/// Heap usage of a phase
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// Number of allocations, counting every reallocation as one
+    pub allocations: u64,
+    /// Total bytes requested by those allocations
+    pub bytes: u64,
+    /// Highest number of bytes live at once, on top of what was live before the phase
+    pub peak: u64,
+}
+
+impl std::fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        )
+    }
+}
+
+struct Bytes(u64);
+
+impl std::fmt::Display for Bytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Runs `f` and reports the heap usage of the current thread while it ran. Only counts
+/// anything when the `alloc-stats` feature installed the counting allocator.
+#[cfg(feature = "alloc-stats")]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    counting::measure(f)
+}
+
+#[cfg(not(feature = "alloc-stats"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    (f(), None)
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    use super::AllocStats;
+
+    // Counters are per thread so days running in parallel don't count each other's
+    // allocations. `live` can go negative on a thread that frees memory allocated elsewhere.
+    thread_local! {
+        static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+        static BYTES: Cell<u64> = const { Cell::new(0) };
+        static LIVE: Cell<i64> = const { Cell::new(0) };
+        static PEAK: Cell<i64> = const { Cell::new(0) };
+    }
+
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn record_alloc(size: usize) {
+        // `try_with` because allocations can still happen while a thread is torn down
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size as u64));
+        record_live(size as i64);
+    }
+
+    fn record_live(change: i64) {
+        let _ = LIVE.try_with(|live| {
+            let now = live.get() + change;
+            live.set(now);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(now)));
+        });
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            record_live(-(layout.size() as i64));
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new.is_null() {
+                record_alloc(new_size);
+                record_live(-(layout.size() as i64));
+            }
+            new
+        }
+    }
+
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+        let allocations = ALLOCATIONS.get();
+        let bytes = BYTES.get();
+        let live = LIVE.get();
+        // Track the peak of this phase only, and restore the outer peak afterwards so
+        // nested measurements don't hide each other
+        let outer_peak = PEAK.replace(live);
+
+        let result = f();
+
+        let peak = PEAK.get();
+        PEAK.set(outer_peak.max(peak));
+
+        let stats = AllocStats {
+            allocations: ALLOCATIONS.get() - allocations,
+            bytes: BYTES.get() - bytes,
+            peak: (peak - live).max(0) as u64,
+        };
+        (result, Some(stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 5 * 1024 * 1024 + 512 * 1024,
+            peak: 512,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 allocations, 5.5 MiB allocated, 512 B peak"
+        );
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn counts_allocations() {
+        let (_, stats) = measure(|| {
+            let mut values: Vec<u64> = Vec::with_capacity(1000);
+            values.extend(0..1000);
+            drop(values);
+            Box::new([0u8; 100])
+        });
+
+        let stats = stats.unwrap();
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.bytes, 8000 + 100);
+        assert_eq!(stats.peak, 8000);
+    }
+
+    #[cfg(not(feature = "alloc-stats"))]
+    #[test]
+    fn disabled() {
+        assert_eq!(measure(|| vec![1, 2, 3]), (vec![1, 2, 3], None));
+    }
+}
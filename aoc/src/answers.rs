@@ -0,0 +1,211 @@
+use std::{collections::HashMap, path::Path};
+
+use aoc_common::Example;
+use serde::{Deserialize, Serialize};
+
+use crate::report::DayReport;
+
+pub const DEFAULT_ANSWERS: &str = "answers.toml";
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    InvalidDay(String),
+}
+
+impl From<std::io::Error> for AnswersError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<toml::de::Error> for AnswersError {
+    fn from(value: toml::de::Error) -> Self {
+        Self::Toml(value)
+    }
+}
+
+impl std::fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Toml(error) => write!(f, "{error}"),
+            Self::InvalidDay(key) => write!(f, "'{key}' is not a day, expected e.g. 'day3'"),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+// Answers can be written as either numbers or strings in the TOML file
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Answer {
+    Integer(i64),
+    String(String),
+}
+
+impl From<Answer> for String {
+    fn from(value: Answer) -> Self {
+        match value {
+            Answer::Integer(n) => n.to_string(),
+            Answer::String(s) => s,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// Expected answers, stored as a TOML table per day:
+///
+/// ```toml
+/// [day1]
+/// part1 = 1145
+/// part2 = 6561
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: HashMap<(u8, u8), String>,
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, AnswersError> {
+        let days: HashMap<String, DayAnswers> = toml::from_str(s)?;
+
+        let mut expected = HashMap::new();
+        for (key, answers) in days {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or(AnswersError::InvalidDay(key))?;
+
+            for (part, answer) in [(1, answers.part1), (2, answers.part2)] {
+                if let Some(answer) = answer {
+                    expected.insert((day, part), answer.into());
+                }
+            }
+        }
+
+        Ok(Self { expected })
+    }
+
+    /// Parses a sidecar file with the answers of a single input: the first line holds the
+    /// answer to part 1 and the second line the answer to part 2. Empty lines are unknown.
+    pub fn parse_sidecar(day: u8, s: &str) -> Self {
+        let expected = (1..=2)
+            .zip(s.lines())
+            .filter(|(_, answer)| !answer.trim().is_empty())
+            .map(|(part, answer)| ((day, part), answer.trim().to_string()))
+            .collect();
+
+        Self { expected }
+    }
+
+    /// The answers an example is known to give
+    pub fn from_example(day: u8, example: &Example) -> Self {
+        let expected = [(1, example.part1), (2, example.part2)]
+            .into_iter()
+            .filter_map(|(part, answer)| Some(((day, part), answer?.to_string())))
+            .collect();
+
+        Self { expected }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Loads the answers from `path`, or from `answers.toml` if it exists when no path is given
+    pub fn load_or_default(path: Option<&Path>) -> Result<Option<Self>, AnswersError> {
+        match path {
+            Some(path) => Self::load(path).map(Some),
+            None if Path::new(DEFAULT_ANSWERS).exists() => {
+                Self::load(Path::new(DEFAULT_ANSWERS)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn expected(&self, day: u8, part: u8) -> Option<&str> {
+        self.expected.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn verify(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.expected(day, part) {
+            Some(expected) if expected == answer.trim() => Verdict::Pass,
+            Some(_) => Verdict::Fail,
+            None => Verdict::Unknown,
+        }
+    }
+
+    // Sets the verdict of every answered part in the report
+    pub fn check(&self, report: &mut DayReport) {
+        for phase in &mut report.phases {
+            if let (Some(part), Some(answer)) = (phase.phase.part(), &phase.answer) {
+                let verdict = self.verify(report.day, part, answer);
+                if verdict == Verdict::Fail {
+                    phase.expected = self.expected(report.day, part).map(str::to_string);
+                }
+                phase.verdict = Some(verdict);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[day1]
+part1 = 3
+part2 = "6"
+
+[day7]
+part2 = 40
+"#;
+
+    #[test]
+    fn verify() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.verify(1, 1, "3"), Verdict::Pass);
+        assert_eq!(answers.verify(1, 2, "6"), Verdict::Pass);
+        assert_eq!(answers.verify(1, 2, "7"), Verdict::Fail);
+        assert_eq!(answers.verify(7, 1, "21"), Verdict::Unknown);
+        assert_eq!(answers.verify(2, 1, "1227775554"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn sidecar() {
+        let answers = Answers::parse_sidecar(5, "707\n");
+        assert_eq!(answers.verify(5, 1, "707"), Verdict::Pass);
+        assert_eq!(answers.verify(5, 2, "14"), Verdict::Unknown);
+        assert_eq!(answers.verify(4, 1, "707"), Verdict::Unknown);
+
+        let answers = Answers::parse_sidecar(5, "\n 14 \n");
+        assert_eq!(answers.verify(5, 1, "3"), Verdict::Unknown);
+        assert_eq!(answers.verify(5, 2, "15"), Verdict::Fail);
+    }
+
+    #[test]
+    fn invalid_day() {
+        assert!(matches!(
+            Answers::parse("[first]\npart1 = 1"),
+            Err(AnswersError::InvalidDay(_))
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::stats::Stats;
+
+pub const DEFAULT_BASELINE: &str = "bench-baseline.json";
+
+// Welch's t statistic above which a difference is considered significant. With the
+// sample sizes used for benchmarking this is well past the 99% confidence level.
+const SIGNIFICANCE: f64 = 3.0;
+
+#[derive(Debug)]
+pub enum BaselineError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl From<std::io::Error> for BaselineError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for BaselineError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl std::fmt::Display for BaselineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Json(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for BaselineError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    samples: usize,
+    mean_ns: f64,
+    stddev_ns: f64,
+    median_ns: f64,
+}
+
+impl From<&Stats> for Entry {
+    fn from(stats: &Stats) -> Self {
+        Self {
+            samples: stats.samples,
+            mean_ns: stats.mean.as_nanos() as f64,
+            stddev_ns: stats.stddev.as_nanos() as f64,
+            median_ns: stats.median.as_nanos() as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    Slower,
+    Faster,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub change: Change,
+    /// Relative difference of the median, `0.4` is 40% slower than the baseline
+    pub relative: f64,
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let percent = self.relative * 100.0;
+        match self.change {
+            Change::Slower => write!(f, "{percent:+.1}% (slower)"),
+            Change::Faster => write!(f, "{percent:+.1}% (faster)"),
+            Change::Unchanged => write!(f, "{percent:+.1}%"),
+        }
+    }
+}
+
+/// Benchmark results of earlier runs, keyed by phase label (e.g. `day8#2`)
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    phases: BTreeMap<String, Entry>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, BaselineError> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BaselineError> {
+        Ok(std::fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    pub fn insert(&mut self, label: String, stats: &Stats) {
+        self.phases.insert(label, stats.into());
+    }
+
+    /// Compares the stats to the baseline using Welch's t-test on the means. The size
+    /// of the change is taken from the medians, which are less affected by outliers,
+    /// and `threshold` is the smallest relative change that is reported at all.
+    pub fn compare(&self, label: &str, stats: &Stats, threshold: f64) -> Option<Comparison> {
+        let old = self.phases.get(label)?;
+        let new = Entry::from(stats);
+
+        let relative = (new.median_ns - old.median_ns) / old.median_ns;
+        let standard_error = (old.stddev_ns.powi(2) / old.samples as f64
+            + new.stddev_ns.powi(2) / new.samples as f64)
+            .sqrt();
+        let significant = if standard_error == 0.0 {
+            new.mean_ns != old.mean_ns
+        } else {
+            ((new.mean_ns - old.mean_ns) / standard_error).abs() > SIGNIFICANCE
+        };
+
+        let change = match (significant && relative.abs() >= threshold, relative > 0.0) {
+            (true, true) => Change::Slower,
+            (true, false) => Change::Faster,
+            (false, _) => Change::Unchanged,
+        };
+
+        Some(Comparison { change, relative })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn stats(mean_ms: u64, stddev_ms: u64) -> Stats {
+        Stats {
+            samples: 50,
+            min: Duration::from_millis(mean_ms),
+            median: Duration::from_millis(mean_ms),
+            mean: Duration::from_millis(mean_ms),
+            stddev: Duration::from_millis(stddev_ms),
+            p95: Duration::from_millis(mean_ms),
+        }
+    }
+
+    #[test]
+    fn regression() {
+        let mut baseline = Baseline::default();
+        baseline.insert("day8#2".to_string(), &stats(64, 2));
+
+        let comparison = baseline.compare("day8#2", &stats(90, 3), 0.05).unwrap();
+        assert_eq!(comparison.change, Change::Slower);
+        assert!((comparison.relative - 26.0 / 64.0).abs() < 1e-9);
+
+        let comparison = baseline.compare("day8#2", &stats(50, 3), 0.05).unwrap();
+        assert_eq!(comparison.change, Change::Faster);
+
+        assert!(baseline.compare("day8#1", &stats(1, 0), 0.05).is_none());
+    }
+
+    #[test]
+    fn noise_is_unchanged() {
+        let mut baseline = Baseline::default();
+        baseline.insert("day6#1".to_string(), &stats(10, 5));
+
+        // Within the noise of the samples
+        let comparison = baseline.compare("day6#1", &stats(11, 5), 0.05).unwrap();
+        assert_eq!(comparison.change, Change::Unchanged);
+
+        // Significant, but smaller than the threshold
+        baseline.insert("day6#2".to_string(), &stats(100, 0));
+        let comparison = baseline.compare("day6#2", &stats(101, 0), 0.05).unwrap();
+        assert_eq!(comparison.change, Change::Unchanged);
+    }
+}
@@ -0,0 +1,271 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use serde::Serialize;
+
+use crate::{
+    answers::Answers,
+    cli::Selection,
+    inputs::InputError,
+    report::{DayReport, Format, Phase, PhaseReport, Status},
+    runner::{self, Day},
+    time,
+};
+
+/// Extension of the files holding the expected answers of an input, e.g. `alice.answer`
+/// next to `alice.txt`
+pub const SIDECAR_EXTENSION: &str = "answer";
+
+/// A phase report together with the input file it was run against
+#[derive(Serialize)]
+struct InputRecord<'a> {
+    input: &'a str,
+    #[serde(flatten)]
+    phase: &'a PhaseReport,
+}
+
+/// Runs a single day against every input in `dir`, checking each against its sidecar
+/// answers when there are any
+pub fn run(dir: &Path, selection: &Selection, format: Format) -> ExitCode {
+    if let Format::Tree = format {
+        eprintln!("--inputs-dir prints a table per input, use `--format text` or `--format json`");
+        return ExitCode::FAILURE;
+    }
+
+    if selection.input().is_some() {
+        eprintln!("--inputs-dir reads every input from the directory, not from stdin");
+        return ExitCode::FAILURE;
+    }
+
+    let mut days = selection.days();
+    let number = match (days.next(), days.next()) {
+        (Some(day), None) => day,
+        _ => {
+            eprintln!("--inputs-dir needs exactly one day, e.g. `aoc run 5 --inputs-dir team/`");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let files = match input_files(dir) {
+        Ok(files) if files.is_empty() => {
+            eprintln!("No inputs found in {}", dir.display());
+            return ExitCode::FAILURE;
+        }
+        Ok(files) => files,
+        Err(error) => {
+            eprintln!("Failed to find inputs: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(day) = Day::find(number) else {
+        if let Format::Text = format {
+            println!("# Day {number}");
+            println!("Not done yet");
+        }
+        return ExitCode::SUCCESS;
+    };
+
+    let mut failed = false;
+    let mut results = Vec::with_capacity(files.len());
+    for path in &files {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let report = run_file(day, path, selection);
+        failed |= report.failed();
+
+        if let Format::Json = format {
+            for phase in &report.phases {
+                let record = InputRecord {
+                    input: &name,
+                    phase,
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&record).expect("Failed to serialize phase report")
+                );
+            }
+        }
+
+        results.push((name, report));
+    }
+
+    if let Format::Text = format {
+        println!("# Day {number}");
+        print_table(&results, selection);
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+// Every file in the directory that isn't a sidecar, sorted by name
+fn input_files(dir: &Path) -> Result<Vec<PathBuf>, InputError> {
+    let io_error = |error| InputError::Io(dir.to_path_buf(), error);
+
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.is_file() && path.extension().is_none_or(|ext| ext != SIDECAR_EXTENSION) {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+fn run_file(day: &Day, path: &Path, selection: &Selection) -> DayReport {
+    let (phases, duration) = time!({
+        match std::fs::read_to_string(path) {
+            Ok(input) => day.run(&input, selection),
+            // The directory was asked for, so an input that can't be read fails like a
+            // parse error would
+            Err(error) => vec![PhaseReport {
+                message: Some(format!("{}: {error}", path.display())),
+                ..PhaseReport::new(day.number, Phase::Parse, Status::Failed)
+            }],
+        }
+    });
+
+    let mut report = DayReport {
+        day: day.number,
+        phases,
+        example: None,
+        duration,
+    };
+
+    if let Ok(sidecar) = std::fs::read_to_string(path.with_extension(SIDECAR_EXTENSION)) {
+        Answers::parse_sidecar(day.number, &sidecar).check(&mut report);
+    }
+
+    report
+}
+
+fn print_table(results: &[(String, DayReport)], selection: &Selection) {
+    let phases = runner::selected_phases(selection).collect::<Vec<_>>();
+
+    let mut header = vec!["input".to_string()];
+    for phase in &phases {
+        match phase.part() {
+            Some(part) => header.extend([format!("part {part}"), "time".to_string()]),
+            None => header.push("parse".to_string()),
+        }
+    }
+
+    let mut rows = vec![header];
+    let mut messages = Vec::new();
+    for (name, report) in results {
+        let mut row = vec![name.clone()];
+        for phase in &phases {
+            row.extend(cells(
+                *phase,
+                report.phases.iter().find(|report| report.phase == *phase),
+            ));
+        }
+        rows.push(row);
+
+        for phase in &report.phases {
+            if let Some(message) = &phase.message {
+                messages.push(match phase.status {
+                    Status::Panicked => format!(
+                        "[PANIC] '{}' panicked on {name}: {message}",
+                        phase.phase.label(report.day)
+                    ),
+                    _ => format!("[ERROR] {name}: {message}"),
+                });
+            }
+        }
+    }
+
+    let columns = rows[0].len();
+    let widths = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+
+    for message in messages {
+        println!("{message}");
+    }
+    println!("-----");
+}
+
+// The cells of a phase in the table: the timing of the parse phase, or the answer and
+// timing of a part. Phases that didn't run show their status instead.
+fn cells(phase: Phase, report: Option<&PhaseReport>) -> Vec<String> {
+    let (outcome, time) = match report {
+        Some(report) => (
+            match report.status {
+                Status::Ok => report.answer_text().unwrap_or_default(),
+                Status::Failed => "error".to_string(),
+                Status::Panicked => "panic".to_string(),
+                Status::Skipped => "skipped".to_string(),
+                Status::Unimplemented => "not done yet".to_string(),
+            },
+            report
+                .duration
+                .map(|duration| format!("{duration:?}"))
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        None => ("-".to_string(), "-".to_string()),
+    };
+
+    match phase.part() {
+        Some(_) => vec![outcome, time],
+        None if report.is_some_and(|report| report.status == Status::Ok) => vec![time],
+        None => vec![outcome],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn phase_cells() {
+        let parse = PhaseReport {
+            duration: Some(Duration::from_micros(52)),
+            ..PhaseReport::new(5, Phase::Parse, Status::Ok)
+        };
+        assert_eq!(cells(Phase::Parse, Some(&parse)), vec!["52µs"]);
+
+        let part = PhaseReport {
+            answer: Some("14".to_string()),
+            duration: Some(Duration::from_micros(3)),
+            verdict: Some(crate::answers::Verdict::Fail),
+            expected: Some("15".to_string()),
+            ..PhaseReport::new(5, Phase::Part2, Status::Ok)
+        };
+        assert_eq!(
+            cells(Phase::Part2, Some(&part)),
+            vec!["14 (fail, expected 15)", "3µs"]
+        );
+
+        let panicked = PhaseReport::new(5, Phase::Part1, Status::Panicked);
+        assert_eq!(cells(Phase::Part1, Some(&panicked)), vec!["panic", "-"]);
+        assert_eq!(cells(Phase::Part2, None), vec!["-", "-"]);
+    }
+}
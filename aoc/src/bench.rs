@@ -0,0 +1,220 @@
+use std::{
+    hint::black_box,
+    path::Path,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use aoc_common::Solution;
+
+use crate::{
+    baseline::{Baseline, Change, DEFAULT_BASELINE},
+    cli::{BenchArgs, Selection},
+    inputs::{INPUTS_DIR, InputError, Inputs},
+    isolate::catch_panic,
+    report::Phase,
+    runner::Day,
+    scaling,
+    stats::Stats,
+};
+
+pub type BenchResults = Vec<(Phase, Stats)>;
+
+/// Repeatedly times a closure after warming it up
+pub struct Sampler {
+    warmup: usize,
+    iterations: usize,
+    budget: Option<Duration>,
+}
+
+impl From<&BenchArgs> for Sampler {
+    fn from(args: &BenchArgs) -> Self {
+        Self {
+            warmup: args.warmup,
+            iterations: args.iterations,
+            budget: args.time,
+        }
+    }
+}
+
+impl Sampler {
+    pub fn sample<T>(&self, mut f: impl FnMut() -> T) -> Stats {
+        for _ in 0..self.warmup {
+            black_box(f());
+        }
+
+        let mut samples = Vec::new();
+        let started = Instant::now();
+
+        loop {
+            let start = Instant::now();
+            black_box(f());
+            samples.push(start.elapsed());
+
+            let done = match self.budget {
+                Some(budget) => started.elapsed() >= budget,
+                None => samples.len() >= self.iterations,
+            };
+            if done {
+                break;
+            }
+        }
+
+        Stats::from_samples(&mut samples).expect("At least one sample is always taken")
+    }
+}
+
+pub fn bench<S: Solution>(
+    input: &str,
+    selection: &Selection,
+    sampler: &Sampler,
+) -> Result<BenchResults, String> {
+    let mut results = Vec::with_capacity(3);
+
+    let solution = S::parse(input).map_err(|error| error.to_string())?;
+
+    results.push((Phase::Parse, sampler.sample(|| S::parse(black_box(input)))));
+
+    if selection.includes_part(1) {
+        results.push((Phase::Part1, sampler.sample(|| solution.part1())));
+    }
+
+    if selection.includes_part(2) {
+        results.push((Phase::Part2, sampler.sample(|| solution.part2())));
+    }
+
+    Ok(results)
+}
+
+pub fn run(args: BenchArgs) -> ExitCode {
+    if !args.sizes.is_empty() {
+        return scaling::run(&args);
+    }
+
+    let sampler = Sampler::from(&args);
+    let inputs = match Inputs::for_selection(INPUTS_DIR, &args.selection) {
+        Ok(inputs) => inputs,
+        Err(error @ InputError::NotSingleDay) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+        Err(error) => {
+            eprintln!("Failed to find inputs: {error}");
+            Inputs::default()
+        }
+    };
+
+    // Baselines are only keyed by day and phase, so the timings of any other input
+    // would be compared with, or overwrite, those of the real one
+    let explicit_input = args.selection.input().is_some();
+    if explicit_input && (args.baseline.is_some() || args.save_baseline) {
+        eprintln!("Baselines can only be used with the inputs in {INPUTS_DIR}/");
+        return ExitCode::FAILURE;
+    }
+
+    let baseline_path = args
+        .baseline
+        .as_deref()
+        .unwrap_or(Path::new(DEFAULT_BASELINE));
+    let mut baseline = if !explicit_input && baseline_path.exists() {
+        Baseline::load(baseline_path).unwrap_or_else(|error| {
+            eprintln!("Failed to load baseline: {error}");
+            Baseline::default()
+        })
+    } else {
+        Baseline::default()
+    };
+
+    let mut regressions = 0;
+    let mut failures = 0;
+    for number in args.selection.days() {
+        println!("# Day {number}");
+
+        let Some(day) = Day::find(number) else {
+            println!("Not done yet");
+            continue;
+        };
+
+        let input = match inputs.read(number) {
+            Ok(input) => input,
+            Err(error) if inputs.is_explicit(number) => {
+                println!("[ERROR] day{number}: {error}");
+                failures += 1;
+                continue;
+            }
+            Err(error) => {
+                println!("Skipped: {error}");
+                continue;
+            }
+        };
+
+        let results = match catch_panic(|| day.bench(&input, &args.selection, &sampler)) {
+            Ok(Ok(results)) => results,
+            Ok(Err(message)) => {
+                println!("[ERROR] day{number}: {message}");
+                failures += 1;
+                continue;
+            }
+            Err(message) => {
+                println!("[PANIC] 'Day {number}' panicked: {message}");
+                failures += 1;
+                continue;
+            }
+        };
+
+        print_header();
+        for (phase, stats) in &results {
+            let label = phase.label(number);
+            let comparison = baseline.compare(&label, stats, args.threshold / 100.0);
+            if comparison.is_some_and(|comparison| comparison.change == Change::Slower) {
+                regressions += 1;
+            }
+            print_stats(&label, stats, comparison.map(|c| c.to_string()));
+
+            if args.save_baseline {
+                baseline.insert(label, stats);
+            }
+        }
+    }
+
+    if args.save_baseline {
+        match baseline.save(baseline_path) {
+            Ok(()) => println!("Saved baseline to {}", baseline_path.display()),
+            Err(error) => {
+                eprintln!("Failed to save baseline: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if regressions > 0 {
+        println!("{regressions} phase(s) got slower than the baseline");
+    }
+
+    if regressions > 0 || failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn print_header() {
+    println!(
+        "{:<12} {:>7} {:>11} {:>11} {:>11} {:>11} {:>11}  baseline",
+        "phase", "samples", "min", "median", "mean", "stddev", "p95"
+    );
+}
+
+fn print_stats(label: &str, stats: &Stats, comparison: Option<String>) {
+    println!(
+        "{:<12} {:>7} {:>11} {:>11} {:>11} {:>11} {:>11}  {}",
+        label,
+        stats.samples,
+        format!("{:.2?}", stats.min),
+        format!("{:.2?}", stats.median),
+        format!("{:.2?}", stats.mean),
+        format!("{:.2?}", stats.stddev),
+        format!("{:.2?}", stats.p95),
+        comparison.as_deref().unwrap_or("-"),
+    );
+}
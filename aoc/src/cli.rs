@@ -0,0 +1,406 @@
+use std::{
+    num::{ParseFloatError, ParseIntError},
+    ops::RangeInclusive,
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::{inputs::InputSource, report::Format};
+
+pub const FIRST_DAY: u8 = 1;
+pub const LAST_DAY: u8 = 12;
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code 2025 solutions")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the selected days (runs every day when no days are given)
+    Run(RunArgs),
+    /// Time each phase of the selected days over many iterations
+    Bench(BenchArgs),
+    /// Create the crate for a new day and register it with the runner
+    New(NewArgs),
+    /// Re-run a day whenever its sources or input change
+    Watch(WatchArgs),
+    /// Run the selected days and write the results as a report
+    Report(ReportArgs),
+    /// Write a randomly generated input for a day to stdout
+    Gen(GenArgs),
+}
+
+#[derive(Debug, Default, Args)]
+pub struct Selection {
+    /// Days to run, e.g. `4`, `2..=6` or `1 3 8`. A `-` reads the input of a single day from stdin
+    pub days: Vec<DayArg>,
+
+    /// Only run the given part
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Read the input of a single day from this file instead, or from stdin for `-`
+    #[arg(long, value_name = "PATH")]
+    pub input: Option<InputSource>,
+}
+
+impl Selection {
+    pub fn includes_day(&self, day: u8) -> bool {
+        let mut ranges = self.ranges().peekable();
+        ranges.peek().is_none() || ranges.any(|range| range.contains(day))
+    }
+
+    fn ranges(&self) -> impl Iterator<Item = &DayRange> {
+        self.days.iter().filter_map(|day| match day {
+            DayArg::Days(range) => Some(range),
+            DayArg::Stdin => None,
+        })
+    }
+
+    /// The input given on the command line, if any
+    pub fn input(&self) -> Option<InputSource> {
+        self.input.clone().or_else(|| {
+            self.days
+                .iter()
+                .any(|day| matches!(day, DayArg::Stdin))
+                .then_some(InputSource::Stdin)
+        })
+    }
+
+    pub fn includes_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u8> {
+        (FIRST_DAY..=LAST_DAY).filter(|day| self.includes_day(*day))
+    }
+}
+
+#[derive(Debug, Default, Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// How answers and timings are written to stdout
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+
+    /// TOML file with the expected answers, defaults to `answers.toml` if it exists
+    #[arg(long)]
+    pub answers: Option<PathBuf>,
+
+    /// Run a single day against every input in this directory and tabulate the results.
+    /// Each input is checked against a sidecar file with the `.answer` extension, if any.
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["input", "answers", "parallel"])]
+    pub inputs_dir: Option<PathBuf>,
+
+    /// Run the selected days on a pool of threads. Results are still printed in day order.
+    #[arg(long)]
+    pub parallel: bool,
+
+    /// Run the examples from the puzzle descriptions instead of the inputs, checking the
+    /// answers they are known to give
+    #[arg(long, conflicts_with_all = ["input", "inputs_dir", "answers", "parallel"])]
+    pub example: bool,
+
+    /// Write all timings as Chrome trace events to this file, for viewing in Perfetto
+    /// or `chrome://tracing`
+    #[arg(long, value_name = "PATH", conflicts_with = "inputs_dir")]
+    pub trace: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Write a self-contained HTML page with the answers and timings to this file
+    #[arg(long, value_name = "PATH")]
+    pub html: PathBuf,
+
+    /// TOML file with the expected answers, defaults to `answers.toml` if it exists
+    #[arg(long)]
+    pub answers: Option<PathBuf>,
+
+    /// Run the selected days on a pool of threads
+    #[arg(long)]
+    pub parallel: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Untimed iterations of each phase before sampling
+    #[arg(long, default_value_t = 3)]
+    pub warmup: usize,
+
+    /// Timed iterations of each phase
+    #[arg(long, short = 'n', default_value_t = 100)]
+    pub iterations: usize,
+
+    /// Sample each phase for this many seconds instead of a fixed number of iterations
+    #[arg(long, value_parser = parse_seconds, conflicts_with = "iterations")]
+    pub time: Option<Duration>,
+
+    /// Baseline file to compare against, defaults to `bench-baseline.json`. Only the
+    /// inputs in `inputs/` are compared with a baseline.
+    #[arg(long, conflicts_with = "input")]
+    pub baseline: Option<PathBuf>,
+
+    /// Store the results in the baseline file for later comparisons
+    #[arg(long, conflicts_with = "input")]
+    pub save_baseline: bool,
+
+    /// Smallest change from the baseline, in percent, that is reported as a regression
+    #[arg(long, default_value_t = 5.0)]
+    pub threshold: f64,
+
+    /// Bench generated inputs of these sizes, e.g. `100,200,400,800`, instead of the real
+    /// inputs and report how the time of each phase grows with the input length
+    #[arg(
+        long,
+        value_delimiter = ',',
+        conflicts_with_all = ["input", "baseline", "save_baseline"]
+    )]
+    pub sizes: Vec<usize>,
+
+    /// Seed of the generated inputs
+    #[arg(long, default_value_t = 0, requires = "sizes")]
+    pub seed: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    /// The day to create
+    #[arg(value_parser = clap::value_parser!(u8).range(FIRST_DAY as i64..=LAST_DAY as i64))]
+    pub day: u8,
+
+    /// Name of the type that parses the input and solves the day
+    #[arg(long, default_value = "Puzzle")]
+    pub name: String,
+
+    /// Root of the workspace
+    #[arg(long, default_value = ".")]
+    pub root: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// The day to watch
+    #[arg(value_parser = clap::value_parser!(u8).range(FIRST_DAY as i64..=LAST_DAY as i64))]
+    pub day: u8,
+
+    /// Only run the given part
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Seconds between checks for changes
+    #[arg(long, value_parser = parse_seconds, default_value = "0.5")]
+    pub interval: Duration,
+}
+
+#[derive(Debug, Args)]
+pub struct GenArgs {
+    /// The day to generate an input for
+    #[arg(value_parser = clap::value_parser!(u8).range(FIRST_DAY as i64..=LAST_DAY as i64))]
+    pub day: u8,
+
+    /// Roughly how many elements, such as lines or rows, the input has
+    #[arg(long, default_value_t = 1000)]
+    pub size: usize,
+
+    /// Seed of the generator. The same seed always generates the same input.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+}
+
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let seconds: f64 = s
+        .parse()
+        .map_err(|error: ParseFloatError| error.to_string())?;
+    Duration::try_from_secs_f64(seconds).map_err(|error| error.to_string())
+}
+
+#[derive(Debug)]
+pub enum DayRangeError {
+    ParseInt(ParseIntError),
+    OutOfBounds(u8),
+    Empty(String),
+}
+
+impl From<ParseIntError> for DayRangeError {
+    fn from(value: ParseIntError) -> Self {
+        Self::ParseInt(value)
+    }
+}
+
+impl std::fmt::Display for DayRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseInt(error) => write!(f, "{error}"),
+            Self::OutOfBounds(day) => {
+                write!(f, "day {day} is not within {FIRST_DAY}..={LAST_DAY}")
+            }
+            Self::Empty(range) => write!(f, "'{range}' does not contain any days"),
+        }
+    }
+}
+
+impl std::error::Error for DayRangeError {}
+
+/// A positional argument of a [`Selection`]: days to run, or `-` to read the input from stdin
+#[derive(Debug, Clone)]
+pub enum DayArg {
+    Days(DayRange),
+    Stdin,
+}
+
+impl FromStr for DayArg {
+    type Err = DayRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "-" => Ok(Self::Stdin),
+            days => days.parse().map(Self::Days),
+        }
+    }
+}
+
+/// A single day (`4`), an inclusive range (`2..=6`) or an exclusive range (`2..6`)
+#[derive(Debug, Clone)]
+pub struct DayRange(RangeInclusive<u8>);
+
+impl DayRange {
+    pub fn contains(&self, day: u8) -> bool {
+        self.0.contains(&day)
+    }
+}
+
+impl FromStr for DayRange {
+    type Err = DayRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let range = if let Some((start, end)) = s.split_once("..=") {
+            start.parse()?..=end.parse()?
+        } else if let Some((start, end)) = s.split_once("..") {
+            let end: u8 = end.parse()?;
+            start.parse()?..=end.saturating_sub(1)
+        } else {
+            let day = s.parse()?;
+            day..=day
+        };
+
+        if range.is_empty() {
+            return Err(DayRangeError::Empty(s.to_string()));
+        }
+
+        for day in [*range.start(), *range.end()] {
+            if !(FIRST_DAY..=LAST_DAY).contains(&day) {
+                return Err(DayRangeError::OutOfBounds(day));
+            }
+        }
+
+        Ok(Self(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_ranges() {
+        assert_eq!(DayRange::from_str("4").unwrap().0, 4..=4);
+        assert_eq!(DayRange::from_str("2..=6").unwrap().0, 2..=6);
+        assert_eq!(DayRange::from_str("2..6").unwrap().0, 2..=5);
+        assert!(DayRange::from_str("0").is_err());
+        assert!(DayRange::from_str("3..=13").is_err());
+        assert!(DayRange::from_str("6..=2").is_err());
+        assert!(DayRange::from_str("day4").is_err());
+    }
+
+    #[test]
+    fn selection() {
+        let selection = Selection {
+            days: vec![DayArg::from_str("2..=4").unwrap()],
+            part: Some(2),
+            input: None,
+        };
+        assert!(selection.includes_day(3));
+        assert!(!selection.includes_day(5));
+        assert!(selection.includes_part(2));
+        assert!(!selection.includes_part(1));
+        assert_eq!(selection.days().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        assert_eq!(selection.input(), None);
+
+        let selection = Selection::default();
+        assert!(selection.includes_day(12));
+        assert!(selection.includes_part(1));
+    }
+
+    #[test]
+    fn explicit_input() {
+        let cli = Cli::try_parse_from(["aoc", "run", "7", "-"]).unwrap();
+        let Some(Command::Run(args)) = cli.command else {
+            panic!("expected the run command");
+        };
+        assert_eq!(args.selection.days().collect::<Vec<_>>(), vec![7]);
+        assert_eq!(args.selection.input(), Some(InputSource::Stdin));
+
+        let cli = Cli::try_parse_from(["aoc", "run", "5", "--input", "team/day5.txt"]).unwrap();
+        let Some(Command::Run(args)) = cli.command else {
+            panic!("expected the run command");
+        };
+        assert_eq!(
+            args.selection.input(),
+            Some(InputSource::Path(PathBuf::from("team/day5.txt")))
+        );
+    }
+
+    #[test]
+    fn inputs_dir_runs_sequentially() {
+        let args = ["aoc", "run", "5", "--inputs-dir", "d", "--parallel"];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn trace_needs_single_input() {
+        assert!(Cli::try_parse_from(["aoc", "run", "5", "--trace", "t.json"]).is_ok());
+        assert!(
+            Cli::try_parse_from(["aoc", "run", "5", "--inputs-dir", "d", "--trace", "t.json"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn bench_sizes() {
+        let cli = Cli::try_parse_from(["aoc", "bench", "8", "--sizes", "100,200,400"]).unwrap();
+        let Some(Command::Bench(args)) = cli.command else {
+            panic!("expected the bench command");
+        };
+        assert_eq!(args.sizes, vec![100, 200, 400]);
+
+        // Generated inputs are never compared with a baseline
+        assert!(Cli::try_parse_from(["aoc", "bench", "--sizes", "10", "--save-baseline"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "bench", "--seed", "3"]).is_err());
+    }
+
+    #[test]
+    fn baseline_needs_real_inputs() {
+        for flag in ["--save-baseline", "--baseline=b.json"] {
+            let args = ["aoc", "bench", "5", "--input", "other.txt", flag];
+            assert!(Cli::try_parse_from(args).is_err());
+        }
+    }
+}
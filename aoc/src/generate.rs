@@ -0,0 +1,31 @@
+use std::{
+    io::{self, Write},
+    process::ExitCode,
+};
+
+use crate::{cli::GenArgs, runner::Day};
+
+pub fn run(args: GenArgs) -> ExitCode {
+    let Some(day) = Day::find(args.day) else {
+        eprintln!("Day {} is not done yet", args.day);
+        return ExitCode::FAILURE;
+    };
+
+    let Some(input) = day.generate(args.size, args.seed) else {
+        eprintln!("Day {} has no input generator", args.day);
+        return ExitCode::FAILURE;
+    };
+
+    let mut stdout = io::stdout().lock();
+    // Inputs end in a newline like a saved puzzle input, unless they already do
+    let newline = if input.ends_with('\n') { "" } else { "\n" };
+    match write!(stdout, "{input}{newline}") {
+        Ok(()) => ExitCode::SUCCESS,
+        // Piping into e.g. `head` closes stdout early, which is fine
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Failed to write the input: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use std::{fmt::Write, process::ExitCode, time::Duration};
+
+use crate::{
+    answers::{Answers, Verdict},
+    cli::ReportArgs,
+    inputs::{INPUTS_DIR, InputError, Inputs},
+    report::{DayReport, Phase, PhaseReport, Status},
+    runner, time,
+};
+
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 60rem; color: #222; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 2rem; }
+th, td { padding: 0.3rem 0.6rem; border-bottom: 1px solid #ddd; text-align: left; }
+td.time { text-align: right; font-variant-numeric: tabular-nums; }
+.badge { font-size: 0.75rem; padding: 0.05rem 0.4rem; border-radius: 0.6rem; color: #fff; }
+.pass { background: #2e7d32; } .fail, .error, .panic { background: #c62828; }
+.unknown, .skipped, .missing { background: #757575; }
+.parse { fill: #90a4ae; } .part1 { fill: #42a5f5; } .part2 { fill: #ab47bc; }
+";
+
+const CHART_WIDTH: f64 = 720.0;
+const LABEL_WIDTH: f64 = 70.0;
+const ROW_HEIGHT: f64 = 24.0;
+
+pub fn run(args: ReportArgs) -> ExitCode {
+    let answers = match Answers::load_or_default(args.answers.as_deref()) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("Failed to load answers: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let inputs = match Inputs::for_selection(INPUTS_DIR, &args.selection) {
+        Ok(inputs) => inputs,
+        Err(error @ InputError::NotSingleDay) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+        Err(error) => {
+            eprintln!("Failed to find inputs: {error}");
+            Inputs::default()
+        }
+    };
+
+    let days = args.selection.days().collect::<Vec<_>>();
+    let (mut reports, elapsed) = time!("All", {
+        if args.parallel {
+            runner::run_parallel(&days, &inputs, &args.selection)
+        } else {
+            days.iter()
+                .map(|number| runner::run_day(*number, &inputs, &args.selection))
+                .collect::<Vec<_>>()
+        }
+    });
+
+    if let Some(answers) = &answers {
+        reports.iter_mut().for_each(|report| answers.check(report));
+    }
+
+    if let Err(error) = std::fs::write(&args.html, render(&reports, elapsed)) {
+        eprintln!("Failed to write {}: {error}", args.html.display());
+        return ExitCode::FAILURE;
+    }
+    println!("Wrote the report to {}", args.html.display());
+
+    if reports.iter().any(DayReport::failed) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Renders a self-contained page with a table of every day and a chart of its phases
+pub fn render(reports: &[DayReport], elapsed: Duration) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Advent of Code 2025</title>\n");
+    let _ = writeln!(html, "<style>{STYLE}</style>\n</head>\n<body>");
+    html.push_str("<h1>Advent of Code 2025</h1>\n");
+    let _ = writeln!(html, "<p>{} days ran in {elapsed:?}.</p>", reports.len());
+
+    render_table(&mut html, reports);
+    html.push_str("<h2>Time per phase</h2>\n");
+    render_chart(&mut html, reports);
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn render_table(html: &mut String, reports: &[DayReport]) {
+    html.push_str("<table>\n<thead>\n<tr><th>Day</th><th>Part 1</th><th>Part 2</th>");
+    html.push_str("<th>Parse time</th><th>Part 1 time</th><th>Part 2 time</th>");
+    html.push_str("<th>Total</th></tr>\n</thead>\n<tbody>\n");
+
+    for report in reports {
+        let _ = write!(html, "<tr><td>{}</td>", report.day);
+        for phase in [Phase::Part1, Phase::Part2] {
+            let _ = write!(html, "<td>{}</td>", answer_cell(part(report, phase)));
+        }
+        for phase in Phase::ALL {
+            let time = find(report, phase)
+                .and_then(|phase| phase.duration)
+                .map(|duration| format!("{duration:?}"))
+                .unwrap_or_default();
+            let _ = write!(html, "<td class=\"time\">{time}</td>");
+        }
+        let _ = writeln!(html, "<td class=\"time\">{:?}</td></tr>", report.duration);
+    }
+
+    html.push_str("</tbody>\n</table>\n");
+}
+
+fn find(report: &DayReport, phase: Phase) -> Option<&PhaseReport> {
+    report.phases.iter().find(|report| report.phase == phase)
+}
+
+// The report of a part, or of the parse phase when it failed or panicked and the part
+// never ran, so its error shows up in the answer cells
+fn part(report: &DayReport, phase: Phase) -> Option<&PhaseReport> {
+    find(report, phase).or_else(|| {
+        find(report, Phase::Parse)
+            .filter(|parse| matches!(parse.status, Status::Failed | Status::Panicked))
+    })
+}
+
+fn answer_cell(phase: Option<&PhaseReport>) -> String {
+    let Some(phase) = phase else {
+        return String::new();
+    };
+
+    let (badge, title) = match phase.status {
+        Status::Ok => match phase.verdict {
+            Some(Verdict::Pass) => ("pass", None),
+            Some(Verdict::Fail) => (
+                "fail",
+                phase
+                    .expected
+                    .as_ref()
+                    .map(|expected| format!("expected {expected}")),
+            ),
+            Some(Verdict::Unknown) => ("unknown", None),
+            None => return escape(phase.answer.as_deref().unwrap_or_default()),
+        },
+        Status::Failed => ("error", phase.message.clone()),
+        Status::Panicked => ("panic", phase.message.clone()),
+        Status::Skipped => ("skipped", phase.message.clone()),
+        Status::Unimplemented => ("missing", None),
+    };
+
+    let title = title
+        .map(|title| format!(" title=\"{}\"", escape(&title)))
+        .unwrap_or_default();
+    format!(
+        "{} <span class=\"badge {badge}\"{title}>{badge}</span>",
+        escape(phase.answer.as_deref().unwrap_or_default())
+    )
+    .trim_start()
+    .to_string()
+}
+
+// One stacked bar per day, split into its phases and scaled to the slowest day
+fn render_chart(html: &mut String, reports: &[DayReport]) {
+    let phase_time = |report: &DayReport, phase| {
+        find(report, phase)
+            .and_then(|phase| phase.duration)
+            .unwrap_or_default()
+            .as_secs_f64()
+    };
+    let slowest = reports
+        .iter()
+        .map(|report| {
+            Phase::ALL
+                .iter()
+                .map(|phase| phase_time(report, *phase))
+                .sum::<f64>()
+        })
+        .fold(0.0, f64::max);
+    let scale = if slowest > 0.0 {
+        (CHART_WIDTH - LABEL_WIDTH) / slowest
+    } else {
+        0.0
+    };
+
+    let height = ROW_HEIGHT * (reports.len() + 1) as f64;
+    let _ = writeln!(
+        html,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{height}\" role=\"img\">"
+    );
+
+    for (row, report) in reports.iter().enumerate() {
+        let y = ROW_HEIGHT * row as f64;
+        let _ = writeln!(
+            html,
+            "<text x=\"0\" y=\"{:.1}\" font-size=\"13\">Day {}</text>",
+            y + ROW_HEIGHT * 0.65,
+            report.day
+        );
+
+        let mut x = LABEL_WIDTH;
+        for phase in Phase::ALL {
+            let Some(duration) = find(report, phase).and_then(|phase| phase.duration) else {
+                continue;
+            };
+            let width = duration.as_secs_f64() * scale;
+            let _ = writeln!(
+                html,
+                "<rect class=\"{}\" x=\"{x:.1}\" y=\"{:.1}\" width=\"{width:.1}\" height=\"{:.1}\"><title>{}: {duration:?}</title></rect>",
+                class(phase),
+                y + 3.0,
+                ROW_HEIGHT - 6.0,
+                phase.label(report.day)
+            );
+            x += width;
+        }
+    }
+
+    // Legend below the bars
+    let y = ROW_HEIGHT * reports.len() as f64 + ROW_HEIGHT * 0.65;
+    for (i, (phase, name)) in [
+        (Phase::Parse, "parse"),
+        (Phase::Part1, "part 1"),
+        (Phase::Part2, "part 2"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let x = LABEL_WIDTH + 90.0 * i as f64;
+        let _ = writeln!(
+            html,
+            "<rect class=\"{}\" x=\"{x}\" y=\"{:.1}\" width=\"12\" height=\"12\"/><text x=\"{}\" y=\"{y:.1}\" font-size=\"13\">{name}</text>",
+            class(phase),
+            y - 10.0,
+            x + 16.0
+        );
+    }
+
+    html.push_str("</svg>\n");
+}
+
+fn class(phase: Phase) -> &'static str {
+    match phase {
+        Phase::Parse => "parse",
+        Phase::Part1 => "part1",
+        Phase::Part2 => "part2",
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> DayReport {
+        let phase = |phase: Phase, micros| PhaseReport {
+            duration: Some(Duration::from_micros(micros)),
+            answer: phase.part().map(|part| format!("answer {part}")),
+            ..PhaseReport::new(3, phase, Status::Ok)
+        };
+
+        DayReport {
+            day: 3,
+            phases: vec![
+                phase(Phase::Parse, 100),
+                PhaseReport {
+                    verdict: Some(Verdict::Fail),
+                    expected: Some("<42>".to_string()),
+                    ..phase(Phase::Part1, 300)
+                },
+                PhaseReport {
+                    verdict: Some(Verdict::Pass),
+                    ..phase(Phase::Part2, 600)
+                },
+            ],
+            example: None,
+            duration: Duration::from_millis(1),
+        }
+    }
+
+    #[test]
+    fn table_cells() {
+        let report = report();
+        assert_eq!(
+            answer_cell(find(&report, Phase::Part1)),
+            "answer 1 <span class=\"badge fail\" title=\"expected &lt;42&gt;\">fail</span>"
+        );
+        assert_eq!(
+            answer_cell(find(&report, Phase::Part2)),
+            "answer 2 <span class=\"badge pass\">pass</span>"
+        );
+
+        let parse_error = DayReport {
+            phases: vec![PhaseReport {
+                message: Some("line 1, column 1: invalid range '10~14'".to_string()),
+                ..PhaseReport::new(5, Phase::Parse, Status::Failed)
+            }],
+            ..report
+        };
+        assert_eq!(
+            answer_cell(part(&parse_error, Phase::Part2)),
+            "<span class=\"badge error\" title=\"line 1, column 1: invalid range &#39;10~14&#39;\">error</span>"
+        );
+
+        let missing = PhaseReport::new(10, Phase::Part1, Status::Unimplemented);
+        assert_eq!(
+            answer_cell(Some(&missing)),
+            "<span class=\"badge missing\">missing</span>"
+        );
+    }
+
+    #[test]
+    fn chart_scales_to_slowest_day() {
+        let html = render(&[report()], Duration::from_millis(1));
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("<script") && !html.contains("<link"));
+        // The only day is the slowest, so its phases fill the bar area
+        let bar = CHART_WIDTH - LABEL_WIDTH;
+        assert!(html.contains(&format!("width=\"{:.1}\"", bar * 0.1)));
+        assert!(html.contains(&format!("width=\"{:.1}\"", bar * 0.6)));
+        assert!(html.contains("<title>day3#2: 600µs</title>"));
+    }
+}
@@ -0,0 +1,212 @@
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::cli::Selection;
+
+pub const INPUTS_DIR: &str = "inputs";
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(PathBuf, std::io::Error),
+    Missing(u8),
+    Ambiguous(u8, Vec<PathBuf>),
+    NotSingleDay,
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, error) => write!(f, "{}: {error}", path.display()),
+            Self::Missing(day) => write!(f, "no input found for day {day}"),
+            Self::Ambiguous(day, paths) => {
+                write!(f, "multiple inputs found for day {day}:")?;
+                for path in paths {
+                    write!(f, " {}", path.display())?;
+                }
+                Ok(())
+            }
+            Self::NotSingleDay => {
+                write!(
+                    f,
+                    "an explicit input needs exactly one day, e.g. `aoc run 7 -`"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// An input given on the command line instead of being found in the inputs directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Self::Path(path) => {
+                std::fs::read_to_string(path).map_err(|error| InputError::Io(path.clone(), error))
+            }
+            Self::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|error| InputError::Io(PathBuf::from("stdin"), error))?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "-" => Self::Stdin,
+            path => Self::Path(PathBuf::from(path)),
+        })
+    }
+}
+
+/// Puzzle inputs found in a directory, keyed by the day number in their file name
+#[derive(Debug, Default)]
+pub struct Inputs {
+    files: BTreeMap<u8, Vec<PathBuf>>,
+    explicit: BTreeMap<u8, InputSource>,
+}
+
+impl Inputs {
+    /// Uses the input given on the command line if there is one, which requires selecting
+    /// a single day. Otherwise discovers every input in `dir`.
+    pub fn for_selection(dir: impl AsRef<Path>, selection: &Selection) -> Result<Self, InputError> {
+        let Some(source) = selection.input() else {
+            return Self::discover(dir);
+        };
+
+        let mut days = selection.days();
+        match (days.next(), days.next()) {
+            (Some(day), None) => Ok(Self::default().with_input(day, source)),
+            _ => Err(InputError::NotSingleDay),
+        }
+    }
+
+    pub fn with_input(mut self, day: u8, source: InputSource) -> Self {
+        self.explicit.insert(day, source);
+        self
+    }
+
+    pub fn discover(dir: impl AsRef<Path>) -> Result<Self, InputError> {
+        let dir = dir.as_ref();
+        let io_error = |error| InputError::Io(dir.to_path_buf(), error);
+
+        let mut files: BTreeMap<u8, Vec<PathBuf>> = BTreeMap::new();
+        for entry in std::fs::read_dir(dir).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            if let Some(day) = day_from_path(&path) {
+                files.entry(day).or_default().push(path);
+            }
+        }
+
+        files.values_mut().for_each(|paths| paths.sort());
+
+        Ok(Self {
+            files,
+            ..Self::default()
+        })
+    }
+
+    pub fn path(&self, day: u8) -> Result<&Path, InputError> {
+        match self.files.get(&day).map(Vec::as_slice) {
+            None | Some([]) => Err(InputError::Missing(day)),
+            Some([path]) => Ok(path),
+            Some(paths) => Err(InputError::Ambiguous(day, paths.to_vec())),
+        }
+    }
+
+    /// Whether the input of `day` was given on the command line rather than discovered
+    pub fn is_explicit(&self, day: u8) -> bool {
+        self.explicit.contains_key(&day)
+    }
+
+    pub fn read(&self, day: u8) -> Result<String, InputError> {
+        if let Some(source) = self.explicit.get(&day) {
+            return source.read();
+        }
+
+        let path = self.path(day)?;
+        std::fs::read_to_string(path).map_err(|error| InputError::Io(path.to_path_buf(), error))
+    }
+}
+
+// Parses the day from file names like `day02.txt`, `10.txt` or `day10.in`
+fn day_from_path(path: &Path) -> Option<u8> {
+    let stem = path.file_stem()?.to_str()?.to_ascii_lowercase();
+    let digits = stem.strip_prefix("day").unwrap_or(&stem);
+
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_from_file_name() {
+        assert_eq!(day_from_path(Path::new("inputs/day02.txt")), Some(2));
+        assert_eq!(day_from_path(Path::new("inputs/10.txt")), Some(10));
+        assert_eq!(day_from_path(Path::new("inputs/day10.in")), Some(10));
+        assert_eq!(day_from_path(Path::new("inputs/Day7")), Some(7));
+        assert_eq!(day_from_path(Path::new("inputs/notes.txt")), None);
+        assert_eq!(day_from_path(Path::new("inputs/day.txt")), None);
+        assert_eq!(day_from_path(Path::new("inputs/day1-example.txt")), None);
+    }
+
+    #[test]
+    fn missing_and_ambiguous() {
+        let inputs = Inputs {
+            files: BTreeMap::from([
+                (1, vec![PathBuf::from("day1.txt")]),
+                (
+                    2,
+                    vec![PathBuf::from("day02.txt"), PathBuf::from("day2.txt")],
+                ),
+            ]),
+            ..Inputs::default()
+        };
+
+        assert_eq!(inputs.path(1).unwrap(), Path::new("day1.txt"));
+        assert!(matches!(inputs.path(2), Err(InputError::Ambiguous(2, _))));
+        assert!(matches!(inputs.path(3), Err(InputError::Missing(3))));
+    }
+
+    #[test]
+    fn explicit_input() {
+        let path = std::env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+        std::fs::write(&path, "3-5\n").unwrap();
+
+        let source = InputSource::from_str(path.to_str().unwrap()).unwrap();
+        let inputs = Inputs::default().with_input(5, source);
+        assert_eq!(inputs.read(5).unwrap(), "3-5\n");
+        assert!(matches!(inputs.read(6), Err(InputError::Missing(6))));
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(InputSource::from_str("-").unwrap(), InputSource::Stdin);
+    }
+}
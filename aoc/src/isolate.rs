@@ -0,0 +1,61 @@
+use std::{
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+// Wraps the current panic hook so panics inside `catch_panic` are recorded instead of
+// printed, while any other panic is still reported as usual.
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                return default_hook(info);
+            }
+
+            let payload = info.payload_as_str().unwrap_or("Box<dyn Any>");
+            let message = match info.location() {
+                Some(location) => format!("{payload} ({location})"),
+                None => payload.to_string(),
+            };
+            MESSAGE.set(Some(message));
+        }));
+    });
+}
+
+/// Runs `f`, turning a panic into an error holding its message and location
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    install_hook();
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+
+    result.map_err(|_| {
+        MESSAGE
+            .take()
+            .unwrap_or_else(|| "panicked without a message".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch_panic(|| 1 + 1), Ok(2));
+
+        let error = catch_panic(|| -> u8 { panic!("malformed input") }).unwrap_err();
+        assert!(error.starts_with("malformed input ("), "{error}");
+        assert!(error.contains("isolate.rs"), "{error}");
+    }
+}
@@ -1,156 +1,119 @@
-use std::str::FromStr;
-
+use std::{process::ExitCode, time::Duration};
+
+use answers::Answers;
+use clap::Parser;
+use cli::{Cli, Command, RunArgs};
+use inputs::{INPUTS_DIR, InputError, Inputs};
+use report::DayReport;
+
+mod alloc;
+mod answers;
+mod baseline;
+mod batch;
+mod bench;
+mod cli;
+mod generate;
+mod html;
+mod inputs;
+mod isolate;
 mod macros;
-
-fn main() {
-    println!("Advent of Code 2025 solutions");
-    let inputs = time!("Get inputs", { get_inputs!() });
-    println!("----");
-
-    time!("All", {
-        day!(1, day1, inputs);
-        day!(2, day2, inputs);
-        day!(3, day3, inputs);
-        day!(4, day4, inputs);
-        day!(5, day5, inputs);
-        day!(6, day6, inputs);
-        day!(7, day7, inputs);
-        day!(8, day8, inputs);
-        day!(9, day9, inputs);
-        day!(10, day10, inputs);
-        day!(11, day11, inputs);
-        day!(12, day12, inputs);
-    });
+mod report;
+mod runner;
+mod scaffold;
+mod scaling;
+mod spans;
+mod stats;
+mod watch;
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Some(Command::Run(args)) => run(args),
+        Some(Command::Bench(args)) => bench::run(args),
+        Some(Command::New(args)) => scaffold::run(args),
+        Some(Command::Watch(args)) => watch::run(args),
+        Some(Command::Report(args)) => html::run(args),
+        Some(Command::Gen(args)) => generate::run(args),
+        None => run(RunArgs::default()),
+    }
 }
 
-fn day1(input: &str) {
-    let instructions = time!("day1#parse", {
-        day1::DialInstructions::parse(input).unwrap()
+fn run(args: RunArgs) -> ExitCode {
+    if let Some(dir) = &args.inputs_dir {
+        return batch::run(dir, &args.selection, args.format);
+    }
+
+    let answers = match Answers::load_or_default(args.answers.as_deref()) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("Failed to load answers: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let format = args.format;
+    format.start();
+
+    let (inputs, elapsed) = time!("Get inputs", {
+        if args.example {
+            Ok(Inputs::default())
+        } else {
+            Inputs::for_selection(INPUTS_DIR, &args.selection)
+        }
     });
-
-    // Solution 1
-    let mut counter = 0;
-    time!("day1#1", {
-        let mut lock = day1::DialLock::new(50, 99);
-        instructions.apply_to_lock_with_fn(&mut lock, |current, _| {
-            if current == 0 {
-                counter += 1;
+    let inputs = match inputs {
+        Ok(inputs) => inputs,
+        Err(error @ InputError::NotSingleDay) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+        Err(error) => {
+            eprintln!("Failed to find inputs: {error}");
+            Inputs::default()
+        }
+    };
+    format.inputs_loaded(elapsed);
+
+    let mut failed = false;
+    let mut summed = Duration::ZERO;
+    let mut finish_day = |mut report: DayReport| {
+        if let Some(answers) = &answers {
+            answers.check(&mut report);
+        }
+        failed |= report.failed();
+        summed += report.duration;
+        format.day(&report);
+    };
+
+    let days = args.selection.days().collect::<Vec<_>>();
+    let (_, elapsed) = time!("All", {
+        if args.example {
+            days.iter()
+                .flat_map(|number| runner::run_examples(*number, &args.selection))
+                .for_each(&mut finish_day);
+        } else if args.parallel {
+            runner::run_parallel(&days, &inputs, &args.selection)
+                .into_iter()
+                .for_each(&mut finish_day);
+        } else {
+            for number in days {
+                finish_day(runner::run_day(number, &inputs, &args.selection));
             }
-        });
-    });
-    println!("Solution 1: {}", counter);
-
-    let mut counter = 0;
-    time!("day1#2", {
-        let mut lock = day1::DialLock::new(50, 99);
-        instructions.apply_to_lock_with_fn(&mut lock, |current, resets| {
-            counter += resets;
-            if current == 0 {
-                counter += 1;
-            }
-        });
-    });
-    println!("Solution 2: {}", counter);
-}
-
-fn day2(input: &str) {
-    let checker = time!("day2#parse", { day2::IdChecker::from_ids(input).unwrap() });
-
-    let angel_numbers_sum = time!("day2#1", { checker.sum_angel_numbers() });
-    println!("Solution 1: {angel_numbers_sum}");
-
-    let invalid_ids_sum = time!("day2#2", { checker.sum_invalid_ids() });
-    println!("Solution 2: {invalid_ids_sum}");
-}
-
-fn day3(input: &str) {
-    let emergency_power = time!("day3#parse", {
-        day3::EmergencyPower::from_str(input).unwrap()
-    });
-
-    let max_joltage = time!("day3#1", { emergency_power.max_joltage() });
-    println!("Solution 1: {max_joltage}");
-
-    let max_joltage_unsafe = time!("day3#2", { emergency_power.max_joltage_unsafe::<12>() });
-    println!("Solution 2: {max_joltage_unsafe}");
-}
-
-fn day4(input: &str) {
-    let mut storage_room = time!("day4#parse", {
-        day4::StorageRoom::from_str(input).unwrap()
+        }
     });
-
-    let accessible_rolls = time!("day4#1", { storage_room.count_accessible_paper_rolls() });
-    println!("Solution 1: {accessible_rolls}");
-
-    let all_accessible_rolls = time!("day4#2", {
-        storage_room.count_accessible_paper_rolls_incrementally()
-    });
-    println!("Solution 2: {all_accessible_rolls}");
-}
-
-fn day5(input: &str) {
-    let database = time!("day5#parse", { day5::Database::from_str(input).unwrap() });
-
-    let fresh_ingredients = time!("day5#1", { database.count_fresh_ingredients() });
-    println!("Solution 1: {fresh_ingredients}");
-
-    let fresh_ingredient_ids = time!("day5#2", { database.count_fresh_ids() });
-    println!("Solution 2: {fresh_ingredient_ids}");
-}
-
-fn day6(input: &str) {
-    let worksheet = time!("day6#parse", { day6::Worksheet::from_str(input).unwrap() });
-
-    let horizontal_sum = time!("day6#1", { worksheet.solve_horizontal_problems_and_sum() });
-    println!("Solution 1: {horizontal_sum}");
-
-    let vertical_sum = time!("day6#2", { worksheet.solve_vertical_problems_and_sum() });
-    println!("Solution 2: {vertical_sum}");
-}
-
-fn day7(input: &str) {
-    let manifold = time!("day7#parse", {
-        day7::TachyonManifold::from_str(input).unwrap()
-    });
-
-    let beam_splits = time!("day7#1", { manifold.calculate_beam_splits() });
-    println!("Solution 1: {beam_splits}");
-
-    let timelines = time!("day7#2", { manifold.calculate_beam_timelines() });
-    println!("Solution 2: {timelines}");
-}
-
-fn day8(input: &str) {
-    let boxes = time!("day8#parse", {
-        day8::JunctionBoxes::from_str(input).unwrap()
-    });
-
-    let circuits_sum = time!("day8#1", { boxes.find_connections(1000) });
-    println!("Solution 1: {circuits_sum}");
-
-    let last_connection_distance = time!("day8#2", { boxes.find_last_distance() });
-    println!("Solution 2: {last_connection_distance}");
-}
-
-fn day9(input: &str) {
-    let floor = time!("day9#parse", { day9::Floor::from_str(input).unwrap() });
-
-    let largest_area = time!("day9#1", { floor.find_largest_area(false) });
-    println!("Solution 1: {largest_area}");
-
-    let largest_area = time!("day9#2", { floor.find_largest_area(true) });
-    println!("Solution 2: {largest_area}");
-}
-
-fn day10(_input: &str) {
-    println!("Not done yet");
-}
-
-fn day11(_input: &str) {
-    println!("Not done yet");
-}
-
-fn day12(_input: &str) {
-    println!("Not done yet");
+    format.finish(elapsed);
+    if args.parallel {
+        format.summed(summed);
+    }
+
+    if let Some(path) = &args.trace
+        && let Err(error) = spans::write_chrome_trace(path)
+    {
+        eprintln!("Failed to write trace to {}: {error}", path.display());
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
 }
@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{alloc::AllocStats, answers::Verdict, spans};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    pub fn part(&self) -> Option<u8> {
+        match self {
+            Self::Parse => None,
+            Self::Part1 => Some(1),
+            Self::Part2 => Some(2),
+        }
+    }
+
+    // Name of the phase as used in timing labels, e.g. `day3#parse` or `day3#1`
+    pub fn label(&self, day: u8) -> String {
+        match self.part() {
+            Some(part) => format!("day{day}#{part}"),
+            None => format!("day{day}#parse"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    Failed,
+    Panicked,
+    Skipped,
+    Unimplemented,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseReport {
+    pub day: u8,
+    pub phase: Phase,
+    pub status: Status,
+    pub answer: Option<String>,
+    #[serde(
+        rename = "duration_ns",
+        serialize_with = "serialize_nanos",
+        deserialize_with = "deserialize_nanos"
+    )]
+    pub duration: Option<Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+    pub verdict: Option<Verdict>,
+    pub expected: Option<String>,
+    pub message: Option<String>,
+}
+
+impl PhaseReport {
+    pub fn new(day: u8, phase: Phase, status: Status) -> Self {
+        Self {
+            day,
+            phase,
+            status,
+            answer: None,
+            duration: None,
+            alloc: None,
+            verdict: None,
+            expected: None,
+            message: None,
+        }
+    }
+
+    // The answer followed by its verdict, e.g. `357 (pass)` or `357 (fail, expected 356)`
+    pub fn answer_text(&self) -> Option<String> {
+        let answer = self.answer.as_ref()?;
+        Some(match (self.verdict, &self.expected) {
+            (Some(Verdict::Fail), Some(expected)) => {
+                format!("{answer} (fail, expected {expected})")
+            }
+            (Some(Verdict::Pass), _) => format!("{answer} (pass)"),
+            (Some(_), _) => format!("{answer} (unknown)"),
+            (None, _) => answer.clone(),
+        })
+    }
+}
+
+fn serialize_nanos<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serializer.serialize_u64(duration.as_nanos() as u64),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_nanos<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_nanos))
+}
+
+pub struct DayReport {
+    pub day: u8,
+    pub phases: Vec<PhaseReport>,
+    /// Name of the example that was run instead of the input
+    pub example: Option<&'static str>,
+    pub duration: Duration,
+}
+
+impl DayReport {
+    // Whether any phase failed, panicked or gave the wrong answer
+    pub fn failed(&self) -> bool {
+        self.phases.iter().any(|phase| {
+            matches!(phase.status, Status::Failed | Status::Panicked)
+                || phase.verdict == Some(Verdict::Fail)
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human readable timings and answers
+    #[default]
+    Text,
+    /// Human readable answers, followed by all timings as a tree
+    Tree,
+    /// One JSON object per line for every day/phase
+    Json,
+}
+
+impl Format {
+    pub fn start(&self) {
+        if let Self::Text | Self::Tree = self {
+            println!("Advent of Code 2025 solutions");
+        }
+    }
+
+    pub fn inputs_loaded(&self, elapsed: Duration) {
+        match self {
+            Self::Text => {
+                println!("[TIMING] 'Get inputs' took: {elapsed:?}");
+                println!("----");
+            }
+            Self::Tree => println!("----"),
+            Self::Json => {}
+        }
+    }
+
+    pub fn day(&self, report: &DayReport) {
+        match self {
+            Self::Text => print_text(report, true),
+            Self::Tree => print_text(report, false),
+            Self::Json => report.phases.iter().for_each(|phase| {
+                let record = PhaseRecord {
+                    example: report.example,
+                    phase,
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&record).expect("Failed to serialize phase report")
+                )
+            }),
+        }
+    }
+
+    pub fn finish(&self, elapsed: Duration) {
+        match self {
+            Self::Text => println!("[TIMING] 'All' took: {elapsed:?}"),
+            Self::Tree => print!("{}", spans::render_tree(&spans::snapshot())),
+            Self::Json => {}
+        }
+    }
+
+    // Total time spent in the days themselves, which exceeds the wall-clock time of
+    // `finish` when days ran in parallel
+    pub fn summed(&self, summed: Duration) {
+        if let Self::Text | Self::Tree = self {
+            println!("[TIMING] 'All days, summed' took: {summed:?}");
+        }
+    }
+}
+
+// A phase report as written by the JSON format, naming the example it ran when there is one
+#[derive(Serialize)]
+struct PhaseRecord<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    example: Option<&'a str>,
+    #[serde(flatten)]
+    phase: &'a PhaseReport,
+}
+
+fn print_text(report: &DayReport, timings: bool) {
+    match report.example {
+        Some(example) => println!("# Day {}, {example}", report.day),
+        None => println!("# Day {}", report.day),
+    }
+
+    for phase in &report.phases {
+        match phase.status {
+            Status::Ok => {
+                if let Some(duration) = phase.duration.filter(|_| timings) {
+                    let alloc = phase
+                        .alloc
+                        .map(|alloc| format!(" ({alloc})"))
+                        .unwrap_or_default();
+                    println!(
+                        "[TIMING] '{}' took: {duration:?}{alloc}",
+                        phase.phase.label(report.day)
+                    );
+                }
+                if let (Some(part), Some(answer)) = (phase.phase.part(), phase.answer_text()) {
+                    println!("Solution {part}: {answer}");
+                }
+            }
+            Status::Failed => {
+                let message = phase.message.as_deref().unwrap_or_default();
+                println!("[ERROR] day{}: {message}", report.day);
+            }
+            Status::Panicked => {
+                let message = phase.message.as_deref().unwrap_or_default();
+                println!(
+                    "[PANIC] '{}' panicked: {message}",
+                    phase.phase.label(report.day)
+                );
+            }
+            Status::Skipped => {
+                let message = phase.message.as_deref().unwrap_or("no input");
+                println!("Skipped: {message}");
+                break;
+            }
+            Status::Unimplemented => {
+                println!("Not done yet");
+                break;
+            }
+        }
+    }
+
+    if timings {
+        println!("[TIMING] 'Day {}' took: {:?}", report.day, report.duration);
+    }
+    println!("-----");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_record() {
+        let report = PhaseReport {
+            answer: Some("3121910778619".to_string()),
+            duration: Some(Duration::from_micros(52)),
+            verdict: Some(Verdict::Pass),
+            ..PhaseReport::new(3, Phase::Part2, Status::Ok)
+        };
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            json,
+            r#"{"day":3,"phase":"part2","status":"ok","answer":"3121910778619","duration_ns":52000,"verdict":"pass","expected":null,"message":null}"#
+        );
+
+        let parsed: PhaseReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.duration, report.duration);
+        assert_eq!(parsed.answer, report.answer);
+        assert_eq!(parsed.verdict, report.verdict);
+    }
+}
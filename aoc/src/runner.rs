@@ -0,0 +1,379 @@
+use std::{
+    fmt::Display,
+    num::NonZero,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use aoc_common::{Example, Rng, Solution};
+
+use crate::{
+    alloc::measure,
+    answers::Answers,
+    bench::{self, BenchResults, Sampler},
+    cli::Selection,
+    inputs::Inputs,
+    isolate::catch_panic,
+    report::{DayReport, Phase, PhaseReport, Status},
+    spans, time,
+};
+
+/// Every solved day. Registering a new day only requires adding it here.
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::DialInstructions>(1),
+    Day::new::<day2::IdChecker>(2),
+    Day::new::<day3::EmergencyPower>(3),
+    Day::new::<day4::StorageRoom>(4),
+    Day::new::<day5::Database>(5),
+    Day::new::<day6::Worksheet>(6),
+    Day::new::<day7::TachyonManifold>(7),
+    Day::new::<day8::JunctionBoxes>(8),
+    Day::new::<day9::Floor>(9),
+];
+
+pub struct Day {
+    pub number: u8,
+    pub examples: &'static [Example],
+    run: fn(u8, &str, &Selection) -> Vec<PhaseReport>,
+    run_example: fn(u8, &str, &Selection) -> Vec<PhaseReport>,
+    generate: fn(usize, &mut Rng) -> Option<String>,
+    bench: fn(&str, &Selection, &Sampler) -> Result<BenchResults, String>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(number: u8) -> Self {
+        Self {
+            number,
+            examples: S::EXAMPLES,
+            run: |day, input, selection| run(day, input, selection, S::parse),
+            run_example: |day, input, selection| run(day, input, selection, S::parse_example),
+            generate: S::generate,
+            bench: bench::bench::<S>,
+        }
+    }
+
+    pub fn find(number: u8) -> Option<&'static Self> {
+        DAYS.iter().find(|day| day.number == number)
+    }
+
+    pub fn run(&self, input: &str, selection: &Selection) -> Vec<PhaseReport> {
+        (self.run)(self.number, input, selection)
+    }
+
+    pub fn run_example(&self, example: &Example, selection: &Selection) -> Vec<PhaseReport> {
+        (self.run_example)(self.number, example.input, selection)
+    }
+
+    /// Generates an input with roughly `size` elements, which is the same for the same seed
+    pub fn generate(&self, size: usize, seed: u64) -> Option<String> {
+        (self.generate)(size, &mut Rng::new(seed))
+    }
+
+    pub fn bench(
+        &self,
+        input: &str,
+        selection: &Selection,
+        sampler: &Sampler,
+    ) -> Result<BenchResults, String> {
+        (self.bench)(input, selection, sampler)
+    }
+}
+
+pub fn run_day(number: u8, inputs: &Inputs, selection: &Selection) -> DayReport {
+    let (phases, duration) = time!(format!("Day {number}"), {
+        match Day::find(number) {
+            Some(day) => match inputs.read(number) {
+                Ok(input) => day.run(&input, selection),
+                // A missing input only fails the day if it was asked for, and then
+                // fails it like a parse error would
+                Err(error) if inputs.is_explicit(number) => vec![PhaseReport {
+                    message: Some(error.to_string()),
+                    ..PhaseReport::new(number, Phase::Parse, Status::Failed)
+                }],
+                Err(error) => not_run(number, selection, Status::Skipped, Some(error.to_string())),
+            },
+            None => not_run(number, selection, Status::Unimplemented, None),
+        }
+    });
+
+    DayReport {
+        day: number,
+        phases,
+        example: None,
+        duration,
+    }
+}
+
+/// Runs a day against each of its examples and checks the answers they are known to give
+pub fn run_examples(number: u8, selection: &Selection) -> Vec<DayReport> {
+    let day = match Day::find(number) {
+        Some(day) if !day.examples.is_empty() => day,
+        day => {
+            let (status, message) = match day {
+                Some(_) => (Status::Skipped, Some("no examples".to_string())),
+                None => (Status::Unimplemented, None),
+            };
+            return vec![DayReport {
+                day: number,
+                phases: not_run(number, selection, status, message),
+                example: None,
+                duration: Duration::ZERO,
+            }];
+        }
+    };
+
+    day.examples
+        .iter()
+        .map(|example| {
+            let (phases, duration) = time!(format!("Day {number}, {}", example.name), {
+                day.run_example(example, selection)
+            });
+
+            let mut report = DayReport {
+                day: number,
+                phases,
+                example: Some(example.name),
+                duration,
+            };
+            Answers::from_example(number, example).check(&mut report);
+            report
+        })
+        .collect()
+}
+
+/// Runs the days on a pool of worker threads, returning their reports in the order of `days`
+pub fn run_parallel(days: &[u8], inputs: &Inputs, selection: &Selection) -> Vec<DayReport> {
+    let workers = thread::available_parallelism()
+        .map_or(1, NonZero::get)
+        .min(days.len());
+    let next = AtomicUsize::new(0);
+    let parent = spans::current();
+
+    let mut reports = thread::scope(|scope| {
+        let workers = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    spans::with_parent(parent, || {
+                        let mut reports = Vec::new();
+                        while let Some(&number) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                            reports.push(run_day(number, inputs, selection));
+                        }
+                        reports
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .expect("panics in days are caught by the runner")
+            })
+            .collect::<Vec<_>>()
+    });
+
+    reports.sort_by_key(|report| days.iter().position(|day| *day == report.day));
+    reports
+}
+
+// Reports every selected phase of a day that could not be run
+fn not_run(
+    day: u8,
+    selection: &Selection,
+    status: Status,
+    message: Option<String>,
+) -> Vec<PhaseReport> {
+    selected_phases(selection)
+        .map(|phase| PhaseReport {
+            message: message.clone(),
+            ..PhaseReport::new(day, phase, status)
+        })
+        .collect()
+}
+
+pub fn selected_phases(selection: &Selection) -> impl Iterator<Item = Phase> {
+    Phase::ALL.into_iter().filter(|phase| {
+        phase
+            .part()
+            .is_none_or(|part| selection.includes_part(part))
+    })
+}
+
+fn run<S: Solution>(
+    day: u8,
+    input: &str,
+    selection: &Selection,
+    parse: fn(&str) -> Result<S, S::Error>,
+) -> Vec<PhaseReport> {
+    let mut phases = Vec::with_capacity(3);
+
+    let solution =
+        match catch_panic(|| time!(Phase::Parse.label(day), { measure(|| parse(input)) })) {
+            Ok(((Ok(solution), alloc), duration)) => {
+                phases.push(PhaseReport {
+                    duration: Some(duration),
+                    alloc,
+                    ..PhaseReport::new(day, Phase::Parse, Status::Ok)
+                });
+                solution
+            }
+            Ok(((Err(error), alloc), duration)) => {
+                phases.push(PhaseReport {
+                    duration: Some(duration),
+                    alloc,
+                    message: Some(error.to_string()),
+                    ..PhaseReport::new(day, Phase::Parse, Status::Failed)
+                });
+                return phases;
+            }
+            Err(message) => {
+                phases.push(PhaseReport {
+                    message: Some(message),
+                    ..PhaseReport::new(day, Phase::Parse, Status::Panicked)
+                });
+                return phases;
+            }
+        };
+
+    if selection.includes_part(1) {
+        phases.push(solve(day, Phase::Part1, || solution.part1()));
+    }
+
+    if selection.includes_part(2) {
+        phases.push(solve(day, Phase::Part2, || solution.part2()));
+    }
+
+    phases
+}
+
+// Times a part and records its answer, or the panic it ran into. Only the part itself is
+// measured, not the bookkeeping of its span or formatting its answer.
+fn solve<A: Display>(day: u8, phase: Phase, f: impl FnOnce() -> A) -> PhaseReport {
+    match catch_panic(|| time!(phase.label(day), { measure(f) })) {
+        Ok(((answer, alloc), duration)) => PhaseReport {
+            answer: Some(answer.to_string()),
+            duration: Some(duration),
+            alloc,
+            ..PhaseReport::new(day, phase, Status::Ok)
+        },
+        Err(message) => PhaseReport {
+            message: Some(message),
+            ..PhaseReport::new(day, phase, Status::Panicked)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{answers::Verdict, inputs::InputSource};
+
+    #[test]
+    fn parallel_keeps_day_order() {
+        let days = [9, 1, 12, 4, 7];
+        let reports = run_parallel(&days, &Inputs::default(), &Selection::default());
+
+        assert_eq!(
+            reports.iter().map(|report| report.day).collect::<Vec<_>>(),
+            days
+        );
+        assert!(
+            reports[1]
+                .phases
+                .iter()
+                .all(|phase| phase.status == Status::Skipped)
+        );
+        assert!(
+            reports[2]
+                .phases
+                .iter()
+                .all(|phase| phase.status == Status::Unimplemented)
+        );
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn allocation_free_part() {
+        let report = solve(6, Phase::Part1, || 4_277_556_u64);
+
+        assert_eq!(report.answer.as_deref(), Some("4277556"));
+        assert_eq!(report.alloc.unwrap().allocations, 0);
+    }
+
+    #[test]
+    fn parse_errors_fail_the_day() {
+        let day = Day::find(5).unwrap();
+        let report = DayReport {
+            day: 5,
+            phases: day.run("10~14\n\n3\n", &Selection::default()),
+            example: None,
+            duration: Duration::ZERO,
+        };
+
+        assert_eq!(report.phases[0].status, Status::Failed);
+        assert!(report.failed());
+    }
+
+    #[test]
+    fn unreadable_explicit_input_fails() {
+        let inputs =
+            Inputs::default().with_input(5, InputSource::Path("does/not/exist.txt".into()));
+        let report = run_day(5, &inputs, &Selection::default());
+
+        assert_eq!(report.phases.len(), 1);
+        assert!(report.failed());
+
+        // Without an explicit input the day is only skipped
+        let report = run_day(5, &Inputs::default(), &Selection::default());
+        assert!(!report.failed());
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        for day in DAYS {
+            for size in [0, 1, 2, 5, 50] {
+                // Days don't have to offer a generator
+                let Some(input) = day.generate(size, 7) else {
+                    continue;
+                };
+                assert_eq!(day.generate(size, 7).unwrap(), input);
+
+                for phase in day.run(&input, &Selection::default()) {
+                    assert_eq!(
+                        phase.status,
+                        Status::Ok,
+                        "day {} of size {size}: {:?}",
+                        day.number,
+                        phase.message
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn examples_pass() {
+        // A freshly scaffolded day has no examples yet
+        for day in DAYS.iter().filter(|day| !day.examples.is_empty()) {
+            let reports = run_examples(day.number, &Selection::default());
+            assert_eq!(reports.len(), day.examples.len());
+
+            for report in reports {
+                let example = report.example.unwrap();
+                for phase in &report.phases {
+                    assert_eq!(phase.status, Status::Ok, "day {} {example}", day.number);
+                    assert_ne!(
+                        phase.verdict,
+                        Some(Verdict::Fail),
+                        "day {} {example}: {:?}",
+                        day.number,
+                        phase.answer
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use crate::cli::NewArgs;
+
+const MANIFEST_TEMPLATE: &str = include_str!("../templates/Cargo.toml.template");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.template");
+
+const RUNNER_MANIFEST: &str = "aoc/Cargo.toml";
+const RUNNER_REGISTRY: &str = "aoc/src/runner.rs";
+const DAYS_START: &str = "pub const DAYS: &[Day] = &[";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Io(PathBuf, std::io::Error),
+    Exists(PathBuf),
+    InvalidName(String),
+    /// A file that has to be edited doesn't look the way it was expected to
+    Unrecognized(PathBuf, &'static str),
+}
+
+impl std::fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, error) => write!(f, "{}: {error}", path.display()),
+            Self::Exists(path) => write!(f, "{} already exists", path.display()),
+            Self::InvalidName(name) => {
+                write!(f, "'{name}' is not an UpperCamelCase type name")
+            }
+            Self::Unrecognized(path, reason) => write!(f, "{}: {reason}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+pub fn run(args: NewArgs) -> ExitCode {
+    match scaffold(&args.root, args.day, &args.name) {
+        Ok(dir) => {
+            println!("Created {}", dir.display());
+            println!(
+                "Put the input in inputs/day{}.txt and run it with `aoc run {}`",
+                args.day, args.day
+            );
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("Failed to create day {}: {error}", args.day);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Creates the crate for a new day and wires it into the runner. Every edit is prepared
+/// before anything is written, so a failure leaves the workspace untouched.
+pub fn scaffold(root: &Path, day: u8, name: &str) -> Result<PathBuf, ScaffoldError> {
+    if !is_type_name(name) {
+        return Err(ScaffoldError::InvalidName(name.to_string()));
+    }
+
+    let dir = root.join("days").join(format!("day{day}"));
+    if dir.exists() {
+        return Err(ScaffoldError::Exists(dir));
+    }
+
+    let manifest_path = root.join(RUNNER_MANIFEST);
+    let manifest = add_dependency(&read(&manifest_path)?, day)
+        .map_err(|reason| ScaffoldError::Unrecognized(manifest_path.clone(), reason))?;
+
+    let registry_path = root.join(RUNNER_REGISTRY);
+    let registry = register(&read(&registry_path)?, day, name)
+        .map_err(|reason| ScaffoldError::Unrecognized(registry_path.clone(), reason))?;
+
+    let src = dir.join("src");
+    fs::create_dir_all(&src).map_err(|error| ScaffoldError::Io(src.clone(), error))?;
+    write(
+        &dir.join("Cargo.toml"),
+        &render(MANIFEST_TEMPLATE, day, name),
+    )?;
+    write(&src.join("lib.rs"), &render(LIB_TEMPLATE, day, name))?;
+    write(&manifest_path, &manifest)?;
+    write(&registry_path, &registry)?;
+
+    Ok(dir)
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|error| ScaffoldError::Io(path.to_path_buf(), error))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|error| ScaffoldError::Io(path.to_path_buf(), error))
+}
+
+fn render(template: &str, day: u8, name: &str) -> String {
+    template
+        .replace("{{day}}", &day.to_string())
+        .replace("{{name}}", name)
+}
+
+fn is_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+// The day number of a `dayN = ...` dependency line
+fn dependency_day(line: &str) -> Option<u8> {
+    let (name, _) = line.split_once('=')?;
+    name.trim().strip_prefix("day")?.parse().ok()
+}
+
+/// Adds `dayN` to the `[dependencies]` of the runner, after the days before it
+fn add_dependency(manifest: &str, day: u8) -> Result<String, &'static str> {
+    let mut lines = manifest.lines().collect::<Vec<_>>();
+    let table = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]")
+        .ok_or("no [dependencies] table")?;
+    let end = lines[table + 1..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| table + 1 + i);
+    let dependencies = &lines[table + 1..end];
+
+    if dependencies
+        .iter()
+        .any(|line| dependency_day(line) == Some(day))
+    {
+        return Err("the day is already a dependency");
+    }
+
+    let position = dependencies
+        .iter()
+        .rposition(|line| dependency_day(line).is_some_and(|other| other < day))
+        .map_or(table + 1, |i| table + 2 + i);
+
+    let dependency = format!("day{day} = {{ path = \"../days/day{day}\" }}");
+    lines.insert(position, &dependency);
+    Ok(lines.join("\n") + "\n")
+}
+
+// The day number of a `Day::new::<dayN::Type>(N),` registry line
+fn registered_day(line: &str) -> Option<u8> {
+    let (number, _) = line
+        .trim()
+        .strip_prefix("Day::new::<day")?
+        .split_once("::")?;
+    number.parse().ok()
+}
+
+/// Adds the day to the `DAYS` registry of the runner, after the days before it
+fn register(registry: &str, day: u8, name: &str) -> Result<String, &'static str> {
+    let mut lines = registry.lines().collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == DAYS_START)
+        .ok_or("no DAYS registry")?;
+    let end = lines[start..]
+        .iter()
+        .position(|line| line.trim() == "];")
+        .map(|i| start + i)
+        .ok_or("the DAYS registry is not closed")?;
+    let days = &lines[start + 1..end];
+
+    if days.iter().any(|line| registered_day(line) == Some(day)) {
+        return Err("the day is already registered");
+    }
+
+    let position = days
+        .iter()
+        .rposition(|line| registered_day(line).is_some_and(|other| other < day))
+        .map_or(start + 1, |i| start + 2 + i);
+
+    let entry = format!("    Day::new::<day{day}::{name}>({day}),");
+    lines.insert(position, &entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dependency() {
+        let manifest = "[package]\nname = \"aoc\"\n\n[dependencies]\naoc-common = { path = \"../common\" }\nday1 = { path = \"../days/day1\" }\nday9 = { path = \"../days/day9\" }\nserde = \"1\"\n";
+
+        let updated = add_dependency(manifest, 10).unwrap();
+        assert!(updated.contains(
+            "day9 = { path = \"../days/day9\" }\nday10 = { path = \"../days/day10\" }\nserde"
+        ));
+
+        let updated = add_dependency(manifest, 4).unwrap();
+        assert!(updated.contains(
+            "day1 = { path = \"../days/day1\" }\nday4 = { path = \"../days/day4\" }\nday9"
+        ));
+
+        assert!(add_dependency(manifest, 9).is_err());
+        assert!(add_dependency("[package]\nname = \"aoc\"\n", 4).is_err());
+    }
+
+    #[test]
+    fn registry() {
+        let registry = "pub const DAYS: &[Day] = &[\n    Day::new::<day1::DialInstructions>(1),\n    Day::new::<day9::Floor>(9),\n];\n";
+
+        let updated = register(registry, 10, "Factory").unwrap();
+        assert!(updated.contains("(9),\n    Day::new::<day10::Factory>(10),\n];"));
+
+        let updated = register(registry, 2, "IdChecker").unwrap();
+        assert!(updated.contains("(1),\n    Day::new::<day2::IdChecker>(2),\n    Day::new::<day9"));
+
+        assert!(register(registry, 9, "Floor").is_err());
+    }
+
+    #[test]
+    fn type_names() {
+        assert!(is_type_name("Factory"));
+        assert!(is_type_name("Day10"));
+        assert!(!is_type_name("factory"));
+        assert!(!is_type_name("Fac-tory"));
+        assert!(!is_type_name(""));
+    }
+}
@@ -0,0 +1,162 @@
+use std::{process::ExitCode, time::Duration};
+
+use crate::{
+    bench::Sampler,
+    cli::BenchArgs,
+    isolate::catch_panic,
+    report::Phase,
+    runner::{self, Day},
+};
+
+/// Benches every selected day on generated inputs of each size and fits how the
+/// median time of each phase grows with the length of the input
+pub fn run(args: &BenchArgs) -> ExitCode {
+    let sampler = Sampler::from(args);
+    let phases = runner::selected_phases(&args.selection).collect::<Vec<_>>();
+
+    let mut failures = 0;
+    for number in args.selection.days() {
+        println!("# Day {number}");
+
+        let Some(day) = Day::find(number) else {
+            println!("Not done yet");
+            continue;
+        };
+
+        // Median time of each phase against the input length, one point per size
+        let mut points = vec![Vec::new(); phases.len()];
+        print_header(&phases);
+
+        for &size in &args.sizes {
+            let Some(input) = day.generate(size, args.seed) else {
+                println!("Skipped: no input generator");
+                break;
+            };
+
+            let results = match catch_panic(|| day.bench(&input, &args.selection, &sampler)) {
+                Ok(Ok(results)) => results,
+                Ok(Err(message)) => {
+                    println!("[ERROR] day{number} at size {size}: {message}");
+                    failures += 1;
+                    break;
+                }
+                Err(message) => {
+                    println!("[PANIC] 'Day {number}' panicked at size {size}: {message}");
+                    failures += 1;
+                    break;
+                }
+            };
+
+            print!("{size:>8} {:>10}", input.len());
+            for (phase, points) in phases.iter().zip(&mut points) {
+                let median = results
+                    .iter()
+                    .find(|(result, _)| result == phase)
+                    .map(|(_, stats)| stats.median);
+                if let Some(median) = median {
+                    points.push((input.len(), median));
+                }
+                print!(
+                    " {:>11}",
+                    median.map_or("-".to_string(), |median| format!("{median:.2?}"))
+                );
+            }
+            println!();
+        }
+
+        print!("{:<19}", "exponent");
+        for points in &points {
+            print!(
+                " {:>11}",
+                exponent(points).map_or("-".to_string(), |exponent| format!("{exponent:.2}"))
+            );
+        }
+        println!();
+    }
+
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn print_header(phases: &[Phase]) {
+    print!("{:>8} {:>10}", "size", "bytes");
+    for phase in phases {
+        let name = match phase.part() {
+            Some(part) => format!("part {part}"),
+            None => "parse".to_string(),
+        };
+        print!(" {name:>11}");
+    }
+    println!();
+}
+
+/// Fits `time ≈ c * length^k` by least squares on a log-log scale and returns `k`. Needs
+/// at least two different lengths with a measurable time.
+fn exponent(points: &[(usize, Duration)]) -> Option<f64> {
+    let logs = points
+        .iter()
+        .filter(|(length, time)| *length > 0 && !time.is_zero())
+        .map(|(length, time)| ((*length as f64).ln(), time.as_secs_f64().ln()))
+        .collect::<Vec<_>>();
+    if logs.len() < 2 {
+        return None;
+    }
+
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let spread = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum::<f64>();
+    if spread == 0.0 {
+        return None;
+    }
+
+    let covariance = logs
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    Some(covariance / spread)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(f: impl Fn(f64) -> f64) -> Vec<(usize, Duration)> {
+        [100, 200, 400, 800, 1600]
+            .into_iter()
+            .map(|length| (length, Duration::from_secs_f64(f(length as f64))))
+            .collect()
+    }
+
+    #[test]
+    fn fitted_exponents() {
+        let linear = exponent(&points(|n| 3e-6 * n)).unwrap();
+        assert!((linear - 1.0).abs() < 1e-3, "{linear}");
+
+        let quadratic = exponent(&points(|n| 2e-9 * n * n)).unwrap();
+        assert!((quadratic - 2.0).abs() < 1e-3, "{quadratic}");
+
+        // n log n grows a bit faster than linear
+        let loglinear = exponent(&points(|n| 1e-7 * n * n.ln())).unwrap();
+        assert!(loglinear > 1.0 && loglinear < 1.3, "{loglinear}");
+    }
+
+    #[test]
+    fn too_few_points() {
+        assert_eq!(exponent(&[]), None);
+        assert_eq!(exponent(&[(100, Duration::from_micros(5))]), None);
+        // A generator that clamps small sizes can give the same length twice
+        let clamped = [
+            (10, Duration::from_micros(5)),
+            (10, Duration::from_micros(6)),
+        ];
+        assert_eq!(exponent(&clamped), None);
+        assert_eq!(
+            exponent(&[(0, Duration::ZERO), (100, Duration::ZERO)]),
+            None
+        );
+    }
+}
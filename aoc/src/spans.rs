@@ -0,0 +1,259 @@
+use std::{
+    cell::RefCell,
+    io,
+    path::Path,
+    sync::{
+        Mutex, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+/// A named, timed region of the run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub name: String,
+    /// Index of the enclosing span
+    pub parent: Option<usize>,
+    /// Start, relative to the first recorded span
+    pub start: Duration,
+    pub duration: Duration,
+    pub thread: u64,
+}
+
+static SPANS: Mutex<Vec<Span>> = Mutex::new(Vec::new());
+static ORIGIN: OnceLock<Instant> = OnceLock::new();
+static NEXT_THREAD: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    // Spans that are open on this thread, innermost last
+    static STACK: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+    static THREAD: u64 = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+}
+
+fn spans() -> std::sync::MutexGuard<'static, Vec<Span>> {
+    // A panic while holding the lock can't leave the spans half updated
+    SPANS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Closes its span when dropped, which also happens when unwinding from a panic
+pub struct SpanGuard {
+    index: usize,
+    start: Instant,
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        spans()[self.index].duration = self.start.elapsed();
+        STACK.with_borrow_mut(|stack| stack.pop());
+    }
+}
+
+/// Opens a span as a child of the innermost open span on this thread
+pub fn enter(name: impl Into<String>) -> SpanGuard {
+    let origin = *ORIGIN.get_or_init(Instant::now);
+    let start = Instant::now();
+    let parent = current();
+
+    let index = {
+        let mut spans = spans();
+        spans.push(Span {
+            name: name.into(),
+            parent,
+            start: start - origin,
+            duration: Duration::ZERO,
+            thread: THREAD.with(|thread| *thread),
+        });
+        spans.len() - 1
+    };
+    STACK.with_borrow_mut(|stack| stack.push(index));
+
+    SpanGuard { index, start }
+}
+
+/// The innermost open span on this thread
+pub fn current() -> Option<usize> {
+    STACK.with_borrow(|stack| stack.last().copied())
+}
+
+/// Runs `f` with spans opened on this thread nested under `parent`, which is how work
+/// handed to another thread stays part of the tree
+pub fn with_parent<T>(parent: Option<usize>, f: impl FnOnce() -> T) -> T {
+    let Some(parent) = parent else {
+        return f();
+    };
+
+    STACK.with_borrow_mut(|stack| stack.push(parent));
+    let result = f();
+    STACK.with_borrow_mut(|stack| stack.pop());
+    result
+}
+
+/// Every span recorded so far
+pub fn snapshot() -> Vec<Span> {
+    spans().clone()
+}
+
+/// Renders the spans as an indented tree, with every span's share of its parent
+pub fn render_tree(spans: &[Span]) -> String {
+    let mut lines = Vec::new();
+    let mut roots = children(spans, None);
+    roots.sort_by_key(|index| spans[*index].start);
+    for root in roots {
+        render_span(spans, root, 0, &mut lines);
+    }
+
+    let width = lines
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    lines
+        .into_iter()
+        .map(|(label, timing)| format!("{label:<width$}  {timing}\n"))
+        .collect()
+}
+
+fn children(spans: &[Span], parent: Option<usize>) -> Vec<usize> {
+    (0..spans.len())
+        .filter(|index| spans[*index].parent == parent)
+        .collect()
+}
+
+fn render_span(spans: &[Span], index: usize, depth: usize, lines: &mut Vec<(String, String)>) {
+    let span = &spans[index];
+    let share = span
+        .parent
+        .map(|parent| spans[parent].duration.as_secs_f64())
+        .filter(|parent| *parent > 0.0)
+        .map(|parent| format!(" {:>6.2}%", span.duration.as_secs_f64() / parent * 100.0))
+        .unwrap_or_default();
+
+    lines.push((
+        format!("{}{}", "  ".repeat(depth), span.name),
+        format!("{:>12}{share}", format!("{:?}", span.duration)),
+    ));
+
+    let mut children = children(spans, Some(index));
+    children.sort_by_key(|child| spans[*child].start);
+    for child in children {
+        render_span(spans, child, depth + 1, lines);
+    }
+}
+
+#[derive(Serialize)]
+struct TraceEvent<'a> {
+    name: &'a str,
+    ph: &'static str,
+    /// Microseconds
+    ts: f64,
+    /// Microseconds
+    dur: f64,
+    pid: u32,
+    tid: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Trace<'a> {
+    trace_events: Vec<TraceEvent<'a>>,
+}
+
+/// Serializes the spans as Chrome trace events, which can be opened in `chrome://tracing`
+/// or Perfetto
+pub fn chrome_trace(spans: &[Span]) -> String {
+    let trace = Trace {
+        trace_events: spans
+            .iter()
+            .map(|span| TraceEvent {
+                name: &span.name,
+                ph: "X",
+                ts: span.start.as_secs_f64() * 1e6,
+                dur: span.duration.as_secs_f64() * 1e6,
+                pid: std::process::id(),
+                tid: span.thread,
+            })
+            .collect(),
+    };
+
+    serde_json::to_string(&trace).expect("Failed to serialize trace")
+}
+
+pub fn write_chrome_trace(path: &Path) -> io::Result<()> {
+    std::fs::write(path, chrome_trace(&snapshot()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(name: &str, parent: Option<usize>, start: u64, duration: u64) -> Span {
+        Span {
+            name: name.to_string(),
+            parent,
+            start: Duration::from_micros(start),
+            duration: Duration::from_micros(duration),
+            thread: 1,
+        }
+    }
+
+    #[test]
+    fn records_nesting() {
+        let thread = THREAD.with(|thread| *thread);
+        {
+            let _outer = enter("outer");
+            let parent = current();
+            let _inner = enter("inner");
+            std::thread::scope(|scope| {
+                scope.spawn(|| with_parent(parent, || drop(enter("worker"))));
+            });
+        }
+
+        let spans = snapshot();
+        let find = |name: &str| spans.iter().rposition(|span| span.name == name).unwrap();
+        let (outer, inner, worker) = (find("outer"), find("inner"), find("worker"));
+
+        assert_eq!(spans[outer].thread, thread);
+        assert_eq!(spans[inner].parent, Some(outer));
+        assert_eq!(spans[worker].parent, Some(outer));
+        assert_ne!(spans[worker].thread, thread);
+        assert!(spans[inner].duration <= spans[outer].duration);
+        assert_eq!(current(), None);
+    }
+
+    #[test]
+    fn tree() {
+        let spans = [
+            span("All", None, 0, 1000),
+            span("Day 2", Some(0), 600, 400),
+            span("Day 1", Some(0), 0, 500),
+            span("day1#parse", Some(2), 0, 125),
+        ];
+
+        assert_eq!(
+            render_tree(&spans),
+            "\
+All                      1ms
+  Day 1                500µs  50.00%
+    day1#parse         125µs  25.00%
+  Day 2                400µs  40.00%
+"
+        );
+    }
+
+    #[test]
+    fn chrome() {
+        let trace = chrome_trace(&[span("All", None, 5, 1500)]);
+        let trace: serde_json::Value = serde_json::from_str(&trace).unwrap();
+        let event = &trace["traceEvents"][0];
+
+        assert_eq!(event["name"], "All");
+        assert_eq!(event["ph"], "X");
+        assert_eq!(event["ts"], 5.0);
+        assert_eq!(event["dur"], 1500.0);
+    }
+}
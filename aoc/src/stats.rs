@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+/// Summary of the samples collected for a single phase
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        samples.sort_unstable();
+
+        let n = samples.len();
+        let nanos = samples.iter().map(|d| d.as_nanos() as f64);
+        let mean = nanos.clone().sum::<f64>() / n as f64;
+        // Sample (n - 1) variance, a single sample has no spread
+        let variance = if n > 1 {
+            nanos.map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        Some(Self {
+            samples: n,
+            min: samples[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+            p95: percentile(samples, 0.95),
+        })
+    }
+}
+
+// Nearest-rank percentile of already sorted samples
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_micros).collect()
+    }
+
+    #[test]
+    fn summary() {
+        let mut samples = micros(&[5, 1, 4, 2, 3]);
+        let stats = Stats::from_samples(&mut samples).unwrap();
+
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        // sqrt(2.5)µs
+        assert_eq!(stats.stddev, Duration::from_nanos(1581));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+    }
+
+    #[test]
+    fn even_samples() {
+        let mut samples = micros(&(1..=20).collect::<Vec<_>>());
+        let stats = Stats::from_samples(&mut samples).unwrap();
+
+        assert_eq!(stats.median, Duration::from_nanos(10_500));
+        assert_eq!(stats.p95, Duration::from_micros(19));
+    }
+
+    #[test]
+    fn single_and_empty() {
+        let stats = Stats::from_samples(&mut micros(&[7])).unwrap();
+        assert_eq!(stats.stddev, Duration::ZERO);
+        assert_eq!(stats.p95, Duration::from_micros(7));
+
+        assert!(Stats::from_samples(&mut []).is_none());
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    cli::WatchArgs,
+    inputs::{INPUTS_DIR, Inputs},
+    report::{PhaseReport, Status},
+};
+
+/// Modification times of every watched file
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+pub fn run(args: WatchArgs) -> ExitCode {
+    let sources = Path::new("days").join(format!("day{}", args.day));
+    if !sources.is_dir() {
+        eprintln!(
+            "Failed to watch day {}: {} does not exist",
+            args.day,
+            sources.display()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    println!(
+        "Watching {} and the day {} input, press Ctrl-C to stop",
+        sources.display(),
+        args.day
+    );
+
+    let mut snapshot = Snapshot::new();
+    let mut previous: Vec<PhaseReport> = Vec::new();
+    loop {
+        let current = watched_files(&sources, args.day);
+        if current != snapshot {
+            snapshot = current;
+            println!("=== Running day {} ===", args.day);
+            if let Some(phases) = rerun(&args) {
+                print_changes(&previous, &phases);
+                previous = phases;
+            }
+        }
+
+        thread::sleep(args.interval);
+    }
+}
+
+fn watched_files(sources: &Path, day: u8) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    collect(sources, &mut snapshot);
+
+    // The input is looked up on every poll, so it is picked up once it's been added
+    if let Ok(inputs) = Inputs::discover(INPUTS_DIR)
+        && let Ok(path) = inputs.path(day)
+    {
+        insert(path, &mut snapshot);
+    }
+
+    snapshot
+}
+
+fn collect(dir: &Path, snapshot: &mut Snapshot) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name != "target") {
+                collect(&path, snapshot);
+            }
+        } else {
+            insert(&path, snapshot);
+        }
+    }
+}
+
+fn insert(path: &Path, snapshot: &mut Snapshot) {
+    if let Ok(modified) = path.metadata().and_then(|metadata| metadata.modified()) {
+        snapshot.insert(path.to_path_buf(), modified);
+    }
+}
+
+/// Rebuilds the runner and runs the day in a child process, since this one can't pick
+/// up a rebuilt day. Returns `None` when the build failed.
+fn rerun(args: &WatchArgs) -> Option<Vec<PhaseReport>> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command.args(["run", "--quiet", "--package", "aoc"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    command.args(["--", "run", &args.day.to_string(), "--format", "json"]);
+    if let Some(part) = args.part {
+        command.args(["--part", &part.to_string()]);
+    }
+
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(error) => {
+            eprintln!("Failed to start cargo: {error}");
+            return None;
+        }
+    };
+
+    let phases = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect::<Vec<PhaseReport>>();
+
+    // Compiler errors and warnings go to stderr, which is only worth showing if nothing ran
+    if phases.is_empty() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        println!("[ERROR] build failed, waiting for changes");
+        return None;
+    }
+
+    Some(phases)
+}
+
+fn print_changes(previous: &[PhaseReport], current: &[PhaseReport]) {
+    for phase in current {
+        let before = previous.iter().find(|before| before.phase == phase.phase);
+        let label = phase.phase.label(phase.day);
+
+        match phase.status {
+            Status::Ok => {
+                if let Some(duration) = phase.duration {
+                    let change = before
+                        .and_then(|before| before.duration)
+                        .map(|before| format!(" ({})", timing_change(before, duration)))
+                        .unwrap_or_default();
+                    println!("[TIMING] '{label}' took: {duration:?}{change}");
+                }
+                if let (Some(part), Some(answer)) = (phase.phase.part(), &phase.answer) {
+                    let change =
+                        answer_change(before.and_then(|before| before.answer.as_deref()), answer);
+                    println!("Solution {part}: {answer}{change}");
+                }
+            }
+            Status::Failed => {
+                let message = phase.message.as_deref().unwrap_or_default();
+                println!("[ERROR] day{}: {message}", phase.day);
+            }
+            Status::Panicked => {
+                let message = phase.message.as_deref().unwrap_or_default();
+                println!("[PANIC] '{label}' panicked: {message}");
+            }
+            Status::Skipped => {
+                let message = phase.message.as_deref().unwrap_or("no input");
+                println!("Skipped: {message}");
+                break;
+            }
+            Status::Unimplemented => {
+                println!("Not done yet");
+                break;
+            }
+        }
+    }
+
+    println!("-----");
+}
+
+fn timing_change(before: Duration, after: Duration) -> String {
+    let relative =
+        (after.as_secs_f64() / before.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0) * 100.0;
+    format!("{relative:+.1}% from {before:?}")
+}
+
+fn answer_change(before: Option<&str>, after: &str) -> String {
+    match before {
+        Some(before) if before != after => format!(" (changed from {before})"),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes() {
+        assert_eq!(
+            timing_change(Duration::from_micros(200), Duration::from_micros(150)),
+            "-25.0% from 200µs"
+        );
+        assert_eq!(
+            timing_change(Duration::from_millis(1), Duration::from_millis(2)),
+            "+100.0% from 1ms"
+        );
+
+        assert_eq!(answer_change(Some("40"), "40"), "");
+        assert_eq!(answer_change(Some("40"), "25"), " (changed from 40)");
+        assert_eq!(answer_change(None, "25"), "");
+    }
+}
@@ -0,0 +1,113 @@
+/// Union-find over the elements `0..len`, with union by rank and path compression
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl DisjointSet {
+    /// Creates `len` sets holding a single element each
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            size: vec![1; len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the representative of the set containing `x`
+    #[inline]
+    pub fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    /// Merges the sets containing `x` and `y`. Returns `false` if they already were the same set.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let px = self.find(x);
+        let py = self.find(y);
+
+        if px == py {
+            return false;
+        }
+
+        let (root, child) = if self.rank[px] < self.rank[py] {
+            (py, px)
+        } else {
+            (px, py)
+        };
+
+        if self.rank[root] == self.rank[child] {
+            self.rank[root] += 1;
+        }
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        true
+    }
+
+    /// Number of elements in the set containing `x`
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Sizes of every set, in order of their representatives
+    pub fn set_sizes(&mut self) -> Vec<usize> {
+        (0..self.len())
+            .filter_map(|i| (self.find(i) == i).then_some(self.size[i]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_and_find() {
+        let mut set = DisjointSet::new(6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+
+        assert_eq!(set.find(0), set.find(3));
+        assert_ne!(set.find(0), set.find(4));
+        assert_eq!(set.size(2), 4);
+        assert_eq!(set.size(5), 1);
+    }
+
+    #[test]
+    fn set_sizes() {
+        let mut set = DisjointSet::new(7);
+        set.union(0, 6);
+        set.union(6, 3);
+        set.union(1, 2);
+
+        let mut sizes = set.set_sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 1, 2, 3]);
+        assert_eq!(sizes.iter().sum::<usize>(), set.len());
+    }
+
+    #[test]
+    fn long_chain() {
+        let mut set = DisjointSet::new(10_000);
+        for i in 1..set.len() {
+            set.union(i - 1, i);
+        }
+        assert_eq!(set.size(0), 10_000);
+        assert_eq!(set.set_sizes(), vec![10_000]);
+    }
+}
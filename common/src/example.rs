@@ -0,0 +1,11 @@
+/// An example from a puzzle description, together with the answers it is known to give.
+///
+/// Examples often only cover one of the parts, so a part without an answer is not checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    /// Name of the example, usually the name of the constant holding its input
+    pub name: &'static str,
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
@@ -0,0 +1,232 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{Point2, Span, Spanned};
+
+/// Offsets to the four orthogonal neighbours of a cell
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets to all eight neighbours of a cell, including the diagonals
+const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    InvalidCell,
+    RaggedRow { expected: usize, found: usize },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCell => write!(f, "invalid cell"),
+            Self::RaggedRow { expected, found } => {
+                write!(f, "row has {found} cells, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// A rectangular grid stored row by row, indexed by `Point2 { x: column, y: row }`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses one row per line, mapping every character to a cell
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, Spanned<GridError>> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let mut found = 0;
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    Spanned::new(
+                        GridError::InvalidCell,
+                        Span::new(row + 1, found + 1, &line[i..i + c.len_utf8()]),
+                    )
+                })?;
+                cells.push(value);
+                found += 1;
+            }
+
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(Spanned::locate(
+                    GridError::RaggedRow { expected, found },
+                    input,
+                    line,
+                ));
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, point: Point2<usize>) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point2<usize>) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` keeps `chunks` from panicking on an empty grid
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every point in the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point2<usize>> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point2<usize>, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The up to four neighbours above, below, left and right of `point`
+    pub fn orthogonal(&self, point: Point2<usize>) -> impl Iterator<Item = Point2<usize>> + use<T> {
+        self.offsets(point, &ORTHOGONAL)
+    }
+
+    /// The up to eight neighbours of `point`, including the diagonals
+    pub fn adjacent(&self, point: Point2<usize>) -> impl Iterator<Item = Point2<usize>> + use<T> {
+        self.offsets(point, &ADJACENT)
+    }
+
+    fn offsets(
+        &self,
+        point: Point2<usize>,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point2<usize>> + use<T> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = point.x.checked_add_signed(dx)?;
+            let y = point.y.checked_add_signed(dy)?;
+            (x < width && y < height).then_some(Point2::new(x, y))
+        })
+    }
+
+    fn index_of(&self, point: Point2<usize>) -> Option<usize> {
+        (point.x < self.width && point.y < self.height).then_some(point.y * self.width + point.x)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2<usize>) -> &Self::Output {
+        self.get(point).expect("point is outside the grid")
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point2<usize>) -> &mut Self::Output {
+        self.get_mut(point).expect("point is outside the grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<Grid<bool>, Spanned<GridError>> {
+        Grid::parse(input, |c| match c {
+            '@' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = parse("..@\n@@.").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[Point2::new(2, 0)]);
+        assert!(!grid[Point2::new(2, 1)]);
+        assert_eq!(grid.get(Point2::new(3, 0)), None);
+        assert_eq!(grid.rows().nth(1), Some([true, true, false].as_slice()));
+        assert_eq!(grid.iter().filter(|(_, cell)| **cell).count(), 3);
+
+        let empty = parse("").unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.points().count(), 0);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = parse("..@\n.x.").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 2: invalid cell 'x'");
+
+        let error = parse("..@\n@@\n...").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: row has 2 cells, expected 3 '@@'"
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let mut grid = Grid::filled(3, 3, 0);
+        grid[Point2::new(1, 1)] = 5;
+        assert_eq!(grid.get(Point2::new(1, 1)), Some(&5));
+
+        assert_eq!(grid.adjacent(Point2::new(1, 1)).count(), 8);
+        assert_eq!(grid.orthogonal(Point2::new(1, 1)).count(), 4);
+
+        let corner = grid.adjacent(Point2::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(
+            corner,
+            vec![Point2::new(1, 0), Point2::new(0, 1), Point2::new(1, 1)]
+        );
+        assert_eq!(
+            grid.orthogonal(Point2::new(2, 2)).collect::<Vec<_>>(),
+            vec![Point2::new(2, 1), Point2::new(1, 2)]
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt::Display;
+
+mod disjoint_set;
+mod example;
+mod grid;
+mod point;
+mod range;
+mod rect;
+mod rng;
+mod span;
+
+pub use disjoint_set::DisjointSet;
+pub use example::Example;
+pub use grid::{Grid, GridError};
+pub use point::{Point2, Point3};
+pub use range::InclusiveRange;
+pub use rect::Rect;
+pub use rng::Rng;
+pub use span::{Span, Spanned};
+
+/// A day's puzzle, as driven by the `aoc` runner.
+///
+/// The runner parses the input once and then solves each selected part against
+/// the parsed value, timing every phase separately.
+pub trait Solution: Sized {
+    type Error: std::error::Error;
+
+    /// The examples from the puzzle description, which `aoc run --example` runs and checks
+    const EXAMPLES: &'static [Example] = &[];
+
+    fn parse(input: &str) -> Result<Self, Self::Error>;
+
+    /// Parses the input of an example. Days whose examples are solved with other
+    /// parameters than the real input override this.
+    fn parse_example(input: &str) -> Result<Self, Self::Error> {
+        Self::parse(input)
+    }
+
+    /// Generates a valid input with roughly `size` elements, such as lines or rows, for
+    /// stress tests and benchmarks. `None` for days without a generator.
+    fn generate(_size: usize, _rng: &mut Rng) -> Option<String> {
+        None
+    }
+
+    fn part1(&self) -> impl Display;
+
+    fn part2(&self) -> impl Display;
+}
@@ -0,0 +1,133 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Sub},
+};
+
+/// A point on a 2D plane or grid
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Squared euclidean distance, which avoids floating point when only comparing
+    pub fn squared_distance(&self, other: &Self) -> T {
+        let dx = other.x - self.x;
+        let dy = other.y - self.y;
+        dx * dx + dy * dy
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// A point in 3D space
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Squared euclidean distance, which avoids floating point when only comparing
+    pub fn squared_distance(&self, other: &Self) -> T {
+        let dx = other.x - self.x;
+        let dy = other.y - self.y;
+        let dz = other.z - self.z;
+        dx * dx + dy * dy + dz * dz
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, 6);
+        assert_eq!(a.squared_distance(&b), 25);
+        assert_eq!(b.squared_distance(&a), 25);
+
+        let a = Point3::new(162isize, 817, 812);
+        let b = Point3::new(425, 690, 689);
+        assert_eq!(a.squared_distance(&b), 263 * 263 + 127 * 127 + 123 * 123);
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(Point2::new(1, 2) + Point2::new(3, 4), Point2::new(4, 6));
+        assert_eq!(
+            Point3::new(5, 5, 5) - Point3::new(1, 2, 3),
+            Point3::new(4, 3, 2)
+        );
+    }
+
+    #[test]
+    fn ordering_and_display() {
+        assert!(Point2::new(1, 9) < Point2::new(2, 0));
+        assert_eq!(Point2::new(7, 1).to_string(), "7,1");
+        assert_eq!(Point3::new(57, 618, 57).to_string(), "57,618,57");
+    }
+}
@@ -0,0 +1,127 @@
+use std::ops::{Add, RangeInclusive, Sub};
+
+/// A range of values including both its start and end.
+///
+/// Unlike [`RangeInclusive`] this is `Copy` and ordered by its start and then its end,
+/// so a sorted collection of ranges can be merged in a single pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InclusiveRange<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> InclusiveRange<T> {
+    pub const fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether the ranges share at least one value
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Grows the range to also cover `other`, and anything in between
+    pub fn extend(&mut self, other: &Self) {
+        self.start = self.start.min(other.start);
+        self.end = self.end.max(other.end);
+    }
+
+    pub fn as_range(&self) -> RangeInclusive<T> {
+        self.start..=self.end
+    }
+
+    /// Merges overlapping ranges, returning sorted and disjoint ranges
+    pub fn merge_all(ranges: impl IntoIterator<Item = Self>) -> Vec<Self> {
+        let mut ranges = ranges
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .collect::<Vec<_>>();
+        ranges.sort_unstable();
+
+        let mut merged: Vec<Self> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if last.overlaps(&range) => last.extend(&range),
+                _ => merged.push(range),
+            }
+        }
+
+        merged
+    }
+}
+
+impl<T> InclusiveRange<T>
+where
+    T: Copy + Ord + From<u8> + Add<Output = T> + Sub<Output = T>,
+{
+    /// Number of values in the range
+    pub fn count(&self) -> T {
+        if self.is_empty() {
+            T::from(0)
+        } else {
+            self.end - self.start + T::from(1)
+        }
+    }
+}
+
+impl<T> From<RangeInclusive<T>> for InclusiveRange<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        Self { start, end }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_and_count() {
+        let range = InclusiveRange::new(10u64, 14);
+        assert!(range.contains(10));
+        assert!(range.contains(14));
+        assert!(!range.contains(15));
+        assert_eq!(range.count(), 5);
+        assert_eq!(range.as_range().sum::<u64>(), 60);
+
+        let empty = InclusiveRange::new(5u64, 4);
+        assert!(empty.is_empty());
+        assert_eq!(empty.count(), 0);
+    }
+
+    #[test]
+    fn overlaps() {
+        let a = InclusiveRange::from(10..=14);
+        assert!(a.overlaps(&InclusiveRange::new(14, 20)));
+        assert!(a.overlaps(&InclusiveRange::new(11, 12)));
+        assert!(a.overlaps(&InclusiveRange::new(0, 100)));
+        assert!(!a.overlaps(&InclusiveRange::new(15, 20)));
+        assert!(!a.overlaps(&InclusiveRange::new(3, 9)));
+    }
+
+    #[test]
+    fn merge_all() {
+        let ranges = [(16, 20), (3, 5), (12, 18), (10, 14), (30, 29)]
+            .map(|(start, end)| InclusiveRange::new(start, end));
+
+        assert_eq!(
+            InclusiveRange::merge_all(ranges),
+            vec![InclusiveRange::new(3, 5), InclusiveRange::new(10, 20)]
+        );
+        assert_eq!(
+            InclusiveRange::merge_all(ranges)
+                .iter()
+                .map(InclusiveRange::count)
+                .sum::<u64>(),
+            14
+        );
+    }
+}
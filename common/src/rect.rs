@@ -0,0 +1,104 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::Point2;
+
+/// An axis-aligned rectangle spanning two inclusive corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Copy + Ord> Rect<T> {
+    /// Creates the rectangle between two opposite corners, in any order
+    pub fn from_corners(a: Point2<T>, b: Point2<T>) -> Self {
+        Self {
+            min: Point2::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point2::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    pub fn contains(&self, point: &Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Whether the rectangles share at least one point, including their edges
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+    }
+
+    /// Whether the insides of the rectangles overlap. Rectangles that only touch
+    /// along an edge or a corner don't.
+    pub fn overlaps_interior(&self, other: &Self) -> bool {
+        self.min.x < other.max.x
+            && self.max.x > other.min.x
+            && self.min.y < other.max.y
+            && self.max.y > other.min.y
+    }
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + Ord + From<u8> + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Number of columns covered, counting both edges
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::from(1)
+    }
+
+    /// Number of rows covered, counting both edges
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::from(1)
+    }
+
+    /// Number of grid cells covered, counting both edges
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corners_and_area() {
+        let rect = Rect::from_corners(Point2::new(11usize, 1), Point2::new(2, 5));
+        assert_eq!(rect.min, Point2::new(2, 1));
+        assert_eq!(rect.max, Point2::new(11, 5));
+        assert_eq!(rect.width(), 10);
+        assert_eq!(rect.height(), 5);
+        assert_eq!(rect.area(), 50);
+
+        let line = Rect::from_corners(Point2::new(3i64, 3), Point2::new(3, 3));
+        assert_eq!(line.area(), 1);
+    }
+
+    #[test]
+    fn contains() {
+        let rect = Rect::from_corners(Point2::new(0, 0), Point2::new(4, 2));
+        assert!(rect.contains(&Point2::new(0, 0)));
+        assert!(rect.contains(&Point2::new(4, 2)));
+        assert!(!rect.contains(&Point2::new(5, 2)));
+    }
+
+    #[test]
+    fn touching_edges() {
+        let a = Rect::from_corners(Point2::new(0, 0), Point2::new(4, 4));
+        let b = Rect::from_corners(Point2::new(4, 0), Point2::new(8, 4));
+        let c = Rect::from_corners(Point2::new(3, 3), Point2::new(8, 8));
+        let d = Rect::from_corners(Point2::new(5, 5), Point2::new(8, 8));
+
+        assert!(a.intersects(&b));
+        assert!(!a.overlaps_interior(&b));
+
+        assert!(a.intersects(&c));
+        assert!(a.overlaps_interior(&c));
+
+        assert!(!a.intersects(&d));
+        assert!(!a.overlaps_interior(&d));
+    }
+}
@@ -0,0 +1,87 @@
+use std::ops::RangeInclusive;
+
+/// A small seedable pseudo-random number generator (SplitMix64), for generating test
+/// inputs that can be reproduced from their seed. Not suitable for anything else.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`. The slight bias towards low numbers doesn't matter for the
+    /// small ranges inputs are generated from.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {start}..={end}");
+        match (end - start).checked_add(1) {
+            Some(len) => start + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    /// An index into a collection of `len` elements
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "no index into an empty collection");
+        self.range(0..=len as u64 - 1) as usize
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Up to `max` randomly chosen pieces joined together, for throwing malformed input
+    /// at parsers
+    pub fn text(&mut self, pieces: &[&str], max: usize) -> String {
+        (0..self.range(0..=max as u64))
+            .map(|_| pieces[self.index(pieces.len())])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..8).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+    }
+
+    #[test]
+    fn range() {
+        let mut rng = Rng::new(1);
+        let values = (0..1000).map(|_| rng.range(3..=5)).collect::<Vec<_>>();
+
+        assert!(values.iter().all(|value| (3..=5).contains(value)));
+        assert!((3..=5).all(|value| values.contains(&value)));
+        assert_eq!(rng.range(9..=9), 9);
+        rng.range(0..=u64::MAX);
+    }
+
+    #[test]
+    fn text() {
+        let mut rng = Rng::new(2);
+        for _ in 0..100 {
+            let text = rng.text(&["ab", "é"], 4);
+            assert!(text.len() <= 8);
+            assert!(text.replace("ab", "").replace('é', "").is_empty());
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt::Display;
+
+/// Location of a piece of text in a puzzle input. Lines and columns start at 1 and
+/// columns are counted in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, text: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            text: text.into(),
+        }
+    }
+
+    /// Locates `text`, which must be a slice of `input`
+    pub fn locate(input: &str, text: &str) -> Self {
+        let offset = (text.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        debug_assert!(offset <= input.len(), "text is not a slice of the input");
+        let before = &input[..offset.min(input.len())];
+
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: text.to_string(),
+        }
+    }
+
+    /// Turns a span found within `part`, a slice of `input`, into a span within `input`
+    pub fn within(self, input: &str, part: &str) -> Self {
+        let base = Self::locate(input, part);
+
+        Self {
+            line: base.line + self.line - 1,
+            column: if self.line == 1 {
+                base.column + self.column - 1
+            } else {
+                self.column
+            },
+            text: self.text,
+        }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// An error together with the location in the input that caused it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned<E> {
+    pub error: E,
+    pub span: Span,
+}
+
+impl<E> Spanned<E> {
+    pub fn new(error: E, span: Span) -> Self {
+        Self { error, span }
+    }
+
+    /// Creates an error caused by `text`, which must be a slice of `input`
+    pub fn locate(error: E, input: &str, text: &str) -> Self {
+        Self::new(error, Span::locate(input, text))
+    }
+
+    /// See [`Span::within`]
+    pub fn within(self, input: &str, part: &str) -> Self {
+        Self {
+            error: self.error,
+            span: self.span.within(input, part),
+        }
+    }
+}
+
+impl<E: Display> Display for Spanned<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.span, self.error)?;
+        if !self.span.text.is_empty() {
+            write!(f, " '{}'", self.span.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for Spanned<E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "3-5\n10~14\n16-20";
+
+    #[test]
+    fn locate() {
+        let line = INPUT.lines().nth(1).unwrap();
+        assert_eq!(Span::locate(INPUT, line), Span::new(2, 1, "10~14"));
+        assert_eq!(Span::locate(INPUT, &line[2..3]), Span::new(2, 3, "~"));
+        assert_eq!(
+            Span::locate(INPUT, &INPUT[INPUT.len()..]),
+            Span::new(3, 6, "")
+        );
+    }
+
+    #[test]
+    fn locate_counts_characters() {
+        let input = "é@x";
+        assert_eq!(Span::locate(input, &input[3..4]), Span::new(1, 3, "x"));
+    }
+
+    #[test]
+    fn within() {
+        let line = INPUT.lines().nth(2).unwrap();
+        let span = Span::locate(line, &line[3..]).within(INPUT, line);
+        assert_eq!(span, Span::new(3, 4, "20"));
+
+        let rest = &INPUT[2..];
+        let span = Span::locate(rest, &rest[3..5]).within(INPUT, rest);
+        assert_eq!(span, Span::new(2, 2, "0~"));
+    }
+
+    #[test]
+    fn display() {
+        let error = Spanned::new("invalid range", Span::new(3, 1, "10~14"));
+        assert_eq!(error.to_string(), "line 3, column 1: invalid range '10~14'");
+
+        let error = Spanned::new("missing a coordinate", Span::new(2, 8, ""));
+        assert_eq!(error.to_string(), "line 2, column 8: missing a coordinate");
+    }
+}
@@ -1,26 +1,37 @@
-use std::{num::ParseIntError, str::FromStr};
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
+
+use aoc_common::{Example, Rng, Solution, Spanned};
 
 #[derive(Debug)]
 pub enum ParseError {
     InvalidCharacter(char),
+    InvalidAmount,
     ParseInt(ParseIntError),
 }
 
-impl From<ParseIntError> for ParseError {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseInt(value)
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCharacter(_) => write!(f, "invalid direction"),
+            Self::InvalidAmount | Self::ParseInt(_) => write!(f, "invalid rotation amount"),
+        }
     }
 }
 
+impl std::error::Error for ParseError {}
+
+const DIAL_START: i16 = 50;
+const DIAL_MAX: i16 = 99;
+
 pub struct DialInstructions {
     instructions: Vec<Rotation>,
 }
 
 impl DialInstructions {
-    pub fn parse(input: &str) -> Result<Self, ParseError> {
+    pub fn parse(input: &str) -> Result<Self, Spanned<ParseError>> {
         let instructions: Vec<Rotation> = input
             .split_ascii_whitespace()
-            .map(Rotation::from_str)
+            .map(|s| Rotation::from_str(s).map_err(|error| error.within(input, s)))
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self { instructions })
@@ -45,17 +56,26 @@ pub enum Rotation {
 }
 
 impl FromStr for Rotation {
-    type Err = ParseError;
+    type Err = Spanned<ParseError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (instruction, amount) = s.split_at(1);
-        let amount: i16 = amount.parse()?;
+        let direction = s.chars().next().map_or(0, char::len_utf8);
+        let (instruction, amount) = s.split_at(direction);
+        // A sign would turn the rotation around, so only plain digits are an amount
+        if !amount.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(Spanned::locate(ParseError::InvalidAmount, s, amount));
+        }
+        let amount: i16 = amount
+            .parse()
+            .map_err(|error| Spanned::locate(ParseError::ParseInt(error), s, amount))?;
 
         match instruction {
             "L" => Ok(Self::Left(amount)),
             "R" => Ok(Self::Right(amount)),
-            invalid => Err(ParseError::InvalidCharacter(
-                invalid.chars().next().unwrap_or(' '),
+            invalid => Err(Spanned::locate(
+                ParseError::InvalidCharacter(invalid.chars().next().unwrap_or(' ')),
+                s,
+                invalid,
             )),
         }
     }
@@ -126,15 +146,88 @@ impl DialLock {
             }
         }
 
+        // Whole rotations from zero end on zero, so the last one doesn't rotate past it
+        if started_at == 0 && self.current == 0 && resets > 0 {
+            resets -= 1;
+        }
+
+        resets
+    }
+
+    // Reference implementation of `rotate` that turns the dial one click at a time
+    pub fn rotate_naive(&mut self, rotation: &Rotation) -> i16 {
+        let (step, amount) = match rotation {
+            Rotation::Right(amount) => (1, *amount),
+            Rotation::Left(amount) => (-1, *amount),
+        };
+
+        let mut resets = 0;
+        for click in 1..=amount {
+            self.current = (self.current + step).rem_euclid(self.max + 1);
+
+            // Ending up at zero isn't rotating past it
+            if self.current == 0 && click != amount {
+                resets += 1;
+            }
+        }
+
         resets
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for DialInstructions {
+    type Error = Spanned<ParseError>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        name: "EXAMPLE1",
+        input: EXAMPLE1,
+        part1: Some("3"),
+        part2: Some("6"),
+    }];
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        DialInstructions::parse(input)
+    }
+
+    // `size` rotations of up to almost ten full turns
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let rotations = (0..size)
+            .map(|_| {
+                let direction = if rng.bool() { 'L' } else { 'R' };
+                format!("{direction}{}", rng.range(1..=999))
+            })
+            .collect::<Vec<_>>();
 
-    const EXAMPLE1: &str = r#"
+        Some(rotations.join("\n"))
+    }
+
+    fn part1(&self) -> impl Display {
+        let mut lock = DialLock::new(DIAL_START, DIAL_MAX);
+        let mut counter = 0;
+        self.apply_to_lock_with_fn(&mut lock, |current, _| {
+            if current == 0 {
+                counter += 1;
+            }
+        });
+        counter
+    }
+
+    fn part2(&self) -> impl Display {
+        let mut lock = DialLock::new(DIAL_START, DIAL_MAX);
+        let mut counter = 0;
+        self.apply_to_lock_with_fn(&mut lock, |current, resets| {
+            // Summed as an `i64`, since long inputs pass zero more often than an `i16` holds
+            counter += i64::from(resets);
+            if current == 0 {
+                counter += 1;
+            }
+        });
+        counter
+    }
+}
+
+/// The example from the puzzle description
+pub const EXAMPLE1: &str = r#"
 L68
 L30
 R48
@@ -147,6 +240,10 @@ R14
 L82
     "#;
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn full_rotations() {
         let mut lock = DialLock::new(10, 99);
@@ -198,6 +295,77 @@ L82
         assert_eq!(lock.current, 0)
     }
 
+    #[test]
+    fn whole_rotations_from_zero() {
+        let mut lock = DialLock::new(0, 99);
+        assert_eq!(lock.rotate(&Rotation::Left(200)), 1);
+        assert_eq!(lock.current, 0);
+        assert_eq!(lock.rotate(&Rotation::Right(100)), 0);
+        assert_eq!(lock.current, 0);
+    }
+
+    #[test]
+    fn rotate_matches_naive() {
+        let mut rng = Rng::new(1);
+
+        for _ in 0..10_000 {
+            // Zero and whole rotations are where the arithmetic goes wrong
+            let start = if rng.bool() {
+                0
+            } else {
+                rng.range(0..=99) as i16
+            };
+            let amount = if rng.bool() {
+                100 * rng.range(0..=5) as i16
+            } else {
+                rng.range(0..=550) as i16
+            };
+            let rotation = if rng.bool() {
+                Rotation::Right(amount)
+            } else {
+                Rotation::Left(amount)
+            };
+
+            let mut lock = DialLock::new(start, DIAL_MAX);
+            let mut naive = DialLock::new(start, DIAL_MAX);
+            assert_eq!(
+                (lock.rotate(&rotation), lock.current),
+                (naive.rotate_naive(&rotation), naive.current),
+                "{rotation:?} from {start}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_never_panics() {
+        let mut rng = Rng::new(1);
+        for _ in 0..10_000 {
+            let input = rng.text(&["L", "R", "0", "7", "99", "\n", " ", "é", "-", "+"], 16);
+            let _ = <DialInstructions as Solution>::parse(&input);
+        }
+    }
+
+    #[test]
+    fn error_location() {
+        let error = DialInstructions::parse("L68\nL30\nX48").err().unwrap();
+        assert_eq!(error.to_string(), "line 3, column 1: invalid direction 'X'");
+
+        let error = DialInstructions::parse("L68 R4a").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 6: invalid rotation amount '4a'"
+        );
+
+        let error = DialInstructions::parse("L68\nR-5").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: invalid rotation amount '-5'"
+        );
+
+        let error = DialInstructions::parse("L68\né30").err().unwrap();
+        assert_eq!(error.to_string(), "line 2, column 1: invalid direction 'é'");
+    }
+
     #[test]
     fn solution_1() {
         let instructions = DialInstructions::parse(EXAMPLE1).unwrap();
@@ -1,4 +1,6 @@
-use std::{num::ParseIntError, ops::RangeInclusive};
+use std::{fmt::Display, num::ParseIntError};
+
+use aoc_common::{Example, InclusiveRange, Rng, Solution, Spanned};
 
 pub type Id = u64;
 const BASE: Id = 10;
@@ -6,44 +8,43 @@ const BASE: Id = 10;
 #[derive(Debug)]
 pub enum ParseError {
     ParseInt(ParseIntError),
+    InvalidRange,
 }
 
-impl From<ParseIntError> for ParseError {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseInt(value)
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseInt(_) => write!(f, "invalid id"),
+            Self::InvalidRange => write!(f, "invalid range, expected 'start-end'"),
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash)]
-pub struct IdRange {
-    start: Id,
-    end: Id,
-}
+impl std::error::Error for ParseError {}
 
-impl IdRange {
-    fn as_range(&self) -> RangeInclusive<Id> {
-        self.start..=self.end
-    }
-}
+pub type IdRange = InclusiveRange<Id>;
 
 pub struct IdChecker {
     ranges: Vec<IdRange>,
 }
 
 impl IdChecker {
-    pub fn from_ids(ids: &str) -> Result<Self, ParseError> {
+    pub fn from_ids(ids: &str) -> Result<Self, Spanned<ParseError>> {
+        let parse_id = |id: &str| {
+            id.parse::<Id>()
+                .map_err(|error| Spanned::locate(ParseError::ParseInt(error), ids, id))
+        };
+
         let ranges = ids
             .trim()
             .split(',')
             .map(|s| {
-                let mut split = s.split('-');
-                let (start, end) = split.next().zip(split.next()).unwrap();
-                Ok(IdRange {
-                    start: start.parse()?,
-                    end: end.parse()?,
-                })
+                let (start, end) = s
+                    .split_once('-')
+                    .ok_or_else(|| Spanned::locate(ParseError::InvalidRange, ids, s))?;
+                Ok(IdRange::new(parse_id(start)?, parse_id(end)?))
             })
-            .collect::<Result<Vec<_>, ParseIntError>>()?;
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self { ranges })
     }
@@ -124,12 +125,48 @@ fn is_invalid_id(n: &Id, digits: u64) -> bool {
     })
 }
 
+impl Solution for IdChecker {
+    type Error = Spanned<ParseError>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        name: "EXAMPLE1",
+        input: EXAMPLE1,
+        part1: Some("1227775554"),
+        part2: Some("4174379265"),
+    }];
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Self::from_ids(input)
+    }
+
+    // `size` ranges of up to a thousand ids each
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let ranges = (0..size.max(1))
+            .map(|_| {
+                let start = rng.range(1..=1_000_000_000);
+                format!("{start}-{}", start + rng.range(0..=1000))
+            })
+            .collect::<Vec<_>>();
+
+        Some(ranges.join(","))
+    }
+
+    fn part1(&self) -> impl Display {
+        self.sum_angel_numbers()
+    }
+
+    fn part2(&self) -> impl Display {
+        self.sum_invalid_ids()
+    }
+}
+
+/// The example from the puzzle description
+pub const EXAMPLE1: &str = r#"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124"#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE1: &str = r#"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124"#;
-
     #[test]
     fn test_count_digits() {
         assert_eq!(count_digits(1300), 4);
@@ -153,6 +190,27 @@ mod tests {
         assert!(is_invalid_id(&1188511885, 10));
     }
 
+    #[test]
+    fn parse_never_panics() {
+        let mut rng = Rng::new(2);
+        for _ in 0..10_000 {
+            let input = rng.text(&["1", "22", "-", ",", "x", "\n", "é", " "], 16);
+            let _ = <IdChecker as Solution>::parse(&input);
+        }
+    }
+
+    #[test]
+    fn error_location() {
+        let error = IdChecker::from_ids("11-22,95-1x5").err().unwrap();
+        assert_eq!(error.to_string(), "line 1, column 10: invalid id '1x5'");
+
+        let error = IdChecker::from_ids("11-22,95").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 7: invalid range, expected 'start-end' '95'"
+        );
+    }
+
     #[test]
     fn solution_1() {
         let checker = IdChecker::from_ids(EXAMPLE1).unwrap();
@@ -1,21 +1,26 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
+
+use aoc_common::{Example, Rng, Solution, Spanned};
 
 #[derive(Debug)]
 pub enum ParseError {
     ParseInt(char),
 }
 
-impl From<char> for ParseError {
-    fn from(value: char) -> Self {
-        Self::ParseInt(value)
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseInt(_) => write!(f, "invalid battery"),
+        }
     }
 }
 
+impl std::error::Error for ParseError {}
+
 type Battery = u64;
 type Joltage = u64;
 
 const BATTERY_RADIX: u32 = 10;
-const MIN_JOLTAGE: Joltage = 1;
 const JOLT_MULTIPLIER_BASE: Joltage = 10;
 
 pub struct EmergencyPower {
@@ -23,13 +28,13 @@ pub struct EmergencyPower {
 }
 
 impl FromStr for EmergencyPower {
-    type Err = ParseError;
+    type Err = Spanned<ParseError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let banks = s
             .lines()
-            .map(BatteryBank::from_str)
-            .collect::<Result<Vec<_>, ParseError>>()?;
+            .map(|line| BatteryBank::from_str(line).map_err(|error| error.within(s, line)))
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self { banks })
     }
@@ -55,14 +60,21 @@ pub struct BatteryBank {
 }
 
 impl FromStr for BatteryBank {
-    type Err = ParseError;
+    type Err = Spanned<ParseError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let batteries = s
-            .trim()
-            .chars()
-            .map(|c| c.to_digit(BATTERY_RADIX).map(|n| n as Battery).ok_or(c))
-            .collect::<Result<Vec<_>, char>>()?;
+        let trimmed = s.trim();
+        let batteries = trimmed
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(BATTERY_RADIX)
+                    .map(|n| n as Battery)
+                    .ok_or_else(|| {
+                        let text = &trimmed[i..i + c.len_utf8()];
+                        Spanned::locate(ParseError::ParseInt(c), s, text)
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self { batteries })
     }
@@ -70,23 +82,22 @@ impl FromStr for BatteryBank {
 
 impl BatteryBank {
     pub fn max_joltage<const AMOUNT: usize>(&self) -> Joltage {
-        let joltages = self.batteries.windows(AMOUNT).fold(
-            [MIN_JOLTAGE; AMOUNT],
-            |mut joltages, battery_joltages| {
-                (0..(AMOUNT - 1)).for_each(|pos| {
-                    if joltages[pos] < battery_joltages[pos] {
-                        joltages[pos] = battery_joltages[pos];
-                        joltages[pos + 1] = MIN_JOLTAGE;
-                    }
-                });
-
-                if joltages[AMOUNT - 1] < battery_joltages[AMOUNT - 1] {
-                    joltages[AMOUNT - 1] = battery_joltages[AMOUNT - 1];
+        // Too few batteries to turn on `AMOUNT` of them
+        if self.batteries.len() < AMOUNT {
+            return 0;
+        }
+
+        let joltages = self
+            .batteries
+            .windows(AMOUNT)
+            .fold([0; AMOUNT], |mut joltages, window| {
+                // A larger battery at `pos` starts over with the batteries after it
+                if let Some(pos) = (0..AMOUNT).find(|&pos| joltages[pos] < window[pos]) {
+                    joltages[pos..].copy_from_slice(&window[pos..]);
                 }
 
                 joltages
-            },
-        );
+            });
 
         joltages
             .into_iter()
@@ -98,17 +109,124 @@ impl BatteryBank {
             })
             .sum()
     }
+
+    // Reference implementation of `max_joltage` that finds the best choice of `n`
+    // batteries among the last ones, for every `n`, going from the back of the bank
+    pub fn max_joltage_naive(&self, amount: usize) -> Joltage {
+        let mut best: Vec<Option<Joltage>> = vec![None; amount + 1];
+        best[0] = Some(0);
+
+        for &battery in self.batteries.iter().rev() {
+            for n in (1..=amount).rev() {
+                let with_battery =
+                    best[n - 1].map(|rest| battery * JOLT_MULTIPLIER_BASE.pow(n as u32 - 1) + rest);
+                best[n] = best[n].max(with_battery);
+            }
+        }
+
+        best[amount].unwrap_or(0)
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for EmergencyPower {
+    type Error = Spanned<ParseError>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        name: "EXAMPLE",
+        input: EXAMPLE,
+        part1: Some("357"),
+        part2: Some("3121910778619"),
+    }];
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Self::from_str(input)
+    }
+
+    // `size` banks of a hundred batteries, like the puzzle input
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        const BANK_SIZE: usize = 100;
+
+        let banks = (0..size)
+            .map(|_| {
+                (0..BANK_SIZE)
+                    .map(|_| char::from(b'0' + rng.range(1..=9) as u8))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        Some(banks.join("\n"))
+    }
+
+    fn part1(&self) -> impl Display {
+        self.max_joltage()
+    }
 
-    const EXAMPLE: &str = r#"987654321111111
+    fn part2(&self) -> impl Display {
+        self.max_joltage_unsafe::<12>()
+    }
+}
+
+/// The example from the puzzle description
+pub const EXAMPLE: &str = r#"987654321111111
 811111111111119
 234234234234278
 818181911112111"#;
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn max_joltage_matches_naive<const AMOUNT: usize>(rng: &mut Rng) {
+        for _ in 0..1000 {
+            let len = rng.range(0..=40) as usize;
+            let bank = BatteryBank {
+                batteries: (0..len).map(|_| rng.range(0..=9)).collect(),
+            };
+
+            assert_eq!(
+                bank.max_joltage::<AMOUNT>(),
+                bank.max_joltage_naive(AMOUNT),
+                "{:?}",
+                bank.batteries
+            );
+        }
+    }
+
+    #[test]
+    fn max_joltage_random() {
+        let mut rng = Rng::new(3);
+        max_joltage_matches_naive::<1>(&mut rng);
+        max_joltage_matches_naive::<2>(&mut rng);
+        max_joltage_matches_naive::<3>(&mut rng);
+        max_joltage_matches_naive::<5>(&mut rng);
+        max_joltage_matches_naive::<12>(&mut rng);
+    }
+
+    #[test]
+    fn zeros_and_short_banks() {
+        let bank = |s| BatteryBank::from_str(s).unwrap();
+        assert_eq!(bank("10").max_joltage::<2>(), 10);
+        assert_eq!(bank("9").max_joltage::<2>(), 0);
+        assert_eq!(bank("1234567890").max_joltage::<2>(), 90);
+    }
+
+    #[test]
+    fn parse_never_panics() {
+        let mut rng = Rng::new(3);
+        for _ in 0..10_000 {
+            let input = rng.text(&["1", "9", "5", "\n", "é", "x", " "], 16);
+            let _ = <EmergencyPower as Solution>::parse(&input);
+        }
+    }
+
+    #[test]
+    fn error_location() {
+        let error = EmergencyPower::from_str("987654321111111\n8111x1111111119")
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "line 2, column 5: invalid battery 'x'");
+    }
+
     #[test]
     fn solution_1() {
         let emergency_power = EmergencyPower::from_str(EXAMPLE).unwrap();
@@ -1,166 +1,84 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
+
+use aoc_common::{Example, Grid, GridError, Point2, Rng, Solution, Spanned};
 
 #[derive(Debug)]
 pub enum ParseError {
-    ParseTile(char),
-}
-
-enum Tile {
-    Empty,
-    PaperRoll,
+    Grid(GridError),
 }
 
-impl TryFrom<char> for Tile {
-    type Error = ParseError;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '.' => Ok(Tile::Empty),
-            '@' => Ok(Tile::PaperRoll),
-            invalid => Err(ParseError::ParseTile(invalid)),
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Grid(GridError::InvalidCell) => write!(f, "invalid tile"),
+            Self::Grid(error) => write!(f, "{error}"),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Coordinate {
-    row: usize,
-    column: usize,
-}
-
-pub struct Row {
-    columns: Vec<Tile>,
-}
-
-impl FromStr for Row {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Row {
-            columns: s
-                .chars()
-                .map(Tile::try_from)
-                .collect::<Result<Vec<_>, _>>()?,
-        })
-    }
-}
+impl std::error::Error for ParseError {}
 
-pub struct Layout {
-    rows: Vec<Row>,
+#[derive(Clone)]
+enum Tile {
+    Empty,
+    PaperRoll,
 }
 
-impl Layout {
-    // Returns lengths of (rows, columns)
-    #[inline(always)]
-    fn get_bounds(&self) -> (usize, usize) {
-        (
-            self.rows.len(),
-            self.rows
-                .first()
-                .map(|row| row.columns.len())
-                .unwrap_or_default(),
-        )
-    }
-
-    #[inline(always)]
-    fn get_tile(&self, coord: &Coordinate) -> Option<&Tile> {
-        self.rows
-            .get(coord.row)
-            .and_then(|row| row.columns.get(coord.column))
-    }
-
-    #[inline(always)]
-    fn get_tile_mut(&mut self, coord: &Coordinate) -> Option<&mut Tile> {
-        self.rows
-            .get_mut(coord.row)
-            .and_then(|row| row.columns.get_mut(coord.column))
-    }
-
-    fn count_adjacent_rolls(&self, coord: &Coordinate) -> i8 {
-        let start = coord.row.saturating_sub(1);
-        let end = coord.row.saturating_add(1);
-        let start_col = coord.column.saturating_sub(1);
-        let end_col = if coord.column < self.rows.first().unwrap().columns.len() {
-            coord.column + 1
-        } else {
-            coord.column
-        };
-
-        let mut adjacent_rolls = 0;
-
-        for row in start..=end {
-            for column in start_col..=end_col {
-                let query_coord = Coordinate { row, column };
-
-                if query_coord != *coord
-                    && let Some(tile) = self.get_tile(&query_coord)
-                    && let Tile::PaperRoll = tile
-                {
-                    adjacent_rolls += 1
-                }
-            }
+impl Tile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Tile::Empty),
+            '@' => Some(Tile::PaperRoll),
+            _ => None,
         }
-
-        adjacent_rolls
     }
 }
 
+#[derive(Clone)]
 pub struct StorageRoom {
-    layout: Layout,
+    layout: Grid<Tile>,
 }
 
 impl FromStr for StorageRoom {
-    type Err = ParseError;
+    type Err = Spanned<ParseError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let layout = Layout {
-            rows: s
-                .lines()
-                .map(Row::from_str)
-                .collect::<Result<Vec<_>, _>>()?,
-        };
+        let layout = Grid::parse(s, Tile::from_char)
+            .map_err(|error| Spanned::new(ParseError::Grid(error.error), error.span))?;
 
         Ok(Self { layout })
     }
 }
 
 impl StorageRoom {
-    pub fn count_accessible_paper_rolls(&self) -> usize {
-        let mut count = 0;
-        let (rows, columns) = self.layout.get_bounds();
-
-        for row in 0..rows {
-            for column in 0..columns {
-                let coord = Coordinate { row, column };
-                let tile = self.layout.get_tile(&coord);
-                if let Some(Tile::PaperRoll) = tile
-                    && self.layout.count_adjacent_rolls(&coord) < 4
-                {
-                    count += 1;
-                }
-            }
-        }
+    fn count_adjacent_rolls(&self, point: Point2<usize>) -> usize {
+        self.layout
+            .adjacent(point)
+            .filter(|&neighbour| matches!(self.layout[neighbour], Tile::PaperRoll))
+            .count()
+    }
+
+    fn is_accessible(&self, point: Point2<usize>) -> bool {
+        matches!(self.layout[point], Tile::PaperRoll) && self.count_adjacent_rolls(point) < 4
+    }
 
-        count
+    pub fn count_accessible_paper_rolls(&self) -> usize {
+        self.layout
+            .points()
+            .filter(|&point| self.is_accessible(point))
+            .count()
     }
 
     pub fn count_accessible_paper_rolls_incrementally(&mut self) -> usize {
         let mut total_count = 0;
-        let (rows, columns) = self.layout.get_bounds();
 
         loop {
             let mut count = 0;
 
-            for row in 0..rows {
-                for column in 0..columns {
-                    let coord = Coordinate { row, column };
-                    if let Some(Tile::PaperRoll) = self.layout.get_tile(&coord)
-                        && self.layout.count_adjacent_rolls(&coord) < 4
-                    {
-                        let tile = self.layout.get_tile_mut(&coord).unwrap();
-                        *tile = Tile::Empty;
-                        count += 1;
-                    }
+            for point in self.layout.points() {
+                if self.is_accessible(point) {
+                    self.layout[point] = Tile::Empty;
+                    count += 1;
                 }
             }
 
@@ -175,11 +93,45 @@ impl StorageRoom {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for StorageRoom {
+    type Error = Spanned<ParseError>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        name: "EXAMPLE",
+        input: EXAMPLE,
+        part1: Some("13"),
+        part2: Some("43"),
+    }];
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Self::from_str(input)
+    }
+
+    // A `size` by `size` room where two out of three tiles hold a paper roll
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let rows = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.range(0..=2) == 0 { '.' } else { '@' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        Some(rows.join("\n"))
+    }
+
+    fn part1(&self) -> impl Display {
+        self.count_accessible_paper_rolls()
+    }
+
+    fn part2(&self) -> impl Display {
+        // Removing rolls mutates the layout, so work on a copy to keep the parsed room reusable
+        self.clone().count_accessible_paper_rolls_incrementally()
+    }
+}
 
-    const EXAMPLE: &str = r#"..@@.@@@@.
+/// The example from the puzzle description
+pub const EXAMPLE: &str = r#"..@@.@@@@.
 @@@.@.@.@@
 @@@@@.@.@@
 @.@@@@..@.
@@ -190,6 +142,31 @@ mod tests {
 .@@@@@@@@.
 @.@.@@@.@."#;
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_never_panics() {
+        let mut rng = Rng::new(4);
+        for _ in 0..10_000 {
+            let input = rng.text(&[".", "@", "\n", "#", "é"], 16);
+            let _ = <StorageRoom as Solution>::parse(&input);
+        }
+    }
+
+    #[test]
+    fn error_location() {
+        let error = StorageRoom::from_str("..@@.\n@@#.@").err().unwrap();
+        assert_eq!(error.to_string(), "line 2, column 3: invalid tile '#'");
+
+        let error = StorageRoom::from_str("..@@.\n@@.").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: row has 3 cells, expected 5 '@@.'"
+        );
+    }
+
     #[test]
     fn solution_1() {
         let storage = StorageRoom::from_str(EXAMPLE).unwrap();
@@ -1,4 +1,6 @@
-use std::{collections::BTreeSet, num::ParseIntError, str::FromStr};
+use std::{collections::BTreeSet, fmt::Display, num::ParseIntError, str::FromStr};
+
+use aoc_common::{Example, InclusiveRange, Rng, Solution, Spanned};
 
 #[derive(Debug)]
 pub enum ParseError {
@@ -7,40 +9,21 @@ pub enum ParseError {
     ParseInt(ParseIntError),
 }
 
-impl From<ParseIntError> for ParseError {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseInt(value)
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingRange => write!(f, "missing the empty line after the fresh ranges"),
+            Self::InvalidRange => write!(f, "invalid range"),
+            Self::ParseInt(_) => write!(f, "invalid ingredient id"),
+        }
     }
 }
 
-pub type IngredientId = u64;
-
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
-pub struct IdRange {
-    start: IngredientId,
-    end: IngredientId,
-}
-
-impl IdRange {
-    pub fn contains(&self, id: IngredientId) -> bool {
-        self.start <= id && self.end >= id
-    }
-
-    pub fn intersects(&self, other: &Self) -> bool {
-        self.contains(other.start) || self.contains(other.end)
-    }
+impl std::error::Error for ParseError {}
 
-    pub fn extend(&mut self, other: &Self) {
-        *self = Self {
-            start: self.start.min(other.start),
-            end: self.end.max(other.end),
-        };
-    }
+pub type IngredientId = u64;
 
-    pub fn span(&self) -> IngredientId {
-        self.start.abs_diff(self.end) + 1
-    }
-}
+pub type IdRange = InclusiveRange<IngredientId>;
 
 pub struct Database {
     ranges: BTreeSet<IdRange>,
@@ -48,28 +31,36 @@ pub struct Database {
 }
 
 impl FromStr for Database {
-    type Err = ParseError;
+    type Err = Spanned<ParseError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
+        let parse_id = |id: &str| {
+            id.parse::<IngredientId>()
+                .map_err(|error| Spanned::locate(ParseError::ParseInt(error), s, id))
+        };
 
         let mut ranges = BTreeSet::new();
         loop {
-            let line = lines.next().ok_or(ParseError::MissingRange)?;
+            let line = lines
+                .next()
+                .ok_or_else(|| Spanned::locate(ParseError::MissingRange, s, &s[s.len()..]))?;
 
             if line.trim().is_empty() {
                 break;
             }
 
-            let mut split = line.split('-');
-            let start = split.next().ok_or(ParseError::InvalidRange)?.parse()?;
-            let end = split.next().ok_or(ParseError::InvalidRange)?.parse()?;
-            ranges.insert(IdRange { start, end });
+            let (start, end) = line
+                .split_once('-')
+                .ok_or_else(|| Spanned::locate(ParseError::InvalidRange, s, line))?;
+            let start = parse_id(start)?;
+            let end = parse_id(end)?;
+            ranges.insert(IdRange::new(start, end));
         }
 
         let mut ids = vec![];
         for id in lines {
-            ids.push(id.trim().parse()?);
+            ids.push(parse_id(id.trim())?);
         }
 
         Ok(Self { ranges, ids })
@@ -94,27 +85,83 @@ impl Database {
     pub fn count_fresh_ids(&self) -> IngredientId {
         let mut counter = 0;
 
-        let mut current = *self.ranges.first().unwrap();
+        let mut ranges = self.ranges.iter();
+        let Some(mut current) = ranges.next().copied() else {
+            return 0;
+        };
 
-        for range in self.ranges.iter().skip(1) {
-            if current.intersects(range) {
+        for range in ranges {
+            if current.overlaps(range) {
                 current.extend(range);
                 continue;
             }
 
-            counter += current.span();
+            counter += current.count();
             current = *range;
         }
 
-        counter + current.span()
+        counter + current.count()
+    }
+
+    // Reference implementation of `count_fresh_ids` that collects every fresh id
+    pub fn count_fresh_ids_naive(&self) -> IngredientId {
+        let ids = self
+            .ranges
+            .iter()
+            .flat_map(IdRange::as_range)
+            .collect::<BTreeSet<_>>();
+
+        ids.len() as IngredientId
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for Database {
+    type Error = Spanned<ParseError>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        name: "EXAMPLE",
+        input: EXAMPLE,
+        part1: Some("3"),
+        part2: Some("14"),
+    }];
 
-    const EXAMPLE: &str = r#"3-5
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Self::from_str(input)
+    }
+
+    // `size` ranges, which increasingly overlap as there are more of them, followed by
+    // `size` ingredient ids
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let ranges = (0..size)
+            .map(|_| {
+                let start = rng.range(1..=1_000_000_000_000);
+                format!("{start}-{}", start + rng.range(0..=10_000_000_000))
+            })
+            .collect::<Vec<_>>();
+        let ids = (0..size)
+            .map(|_| rng.range(1..=1_010_000_000_000).to_string())
+            .collect::<Vec<_>>();
+
+        // Without ranges the input starts right at the blank line before the ids
+        let separator = if ranges.is_empty() { "\n" } else { "\n\n" };
+        Some(format!(
+            "{}{separator}{}",
+            ranges.join("\n"),
+            ids.join("\n")
+        ))
+    }
+
+    fn part1(&self) -> impl Display {
+        self.count_fresh_ingredients()
+    }
+
+    fn part2(&self) -> impl Display {
+        self.count_fresh_ids()
+    }
+}
+
+/// The example from the puzzle description
+pub const EXAMPLE: &str = r#"3-5
 10-14
 16-20
 12-18
@@ -126,6 +173,57 @@ mod tests {
 17
 32"#;
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_fresh_ids_matches_naive() {
+        let mut rng = Rng::new(5);
+
+        for _ in 0..1000 {
+            // Few, short ranges close together, so they often touch, overlap or nest
+            let ranges = (0..rng.range(0..=8))
+                .map(|_| {
+                    let start = rng.range(0..=40);
+                    IdRange::new(start, start + rng.range(0..=10))
+                })
+                .collect::<BTreeSet<_>>();
+            let database = Database {
+                ranges,
+                ids: Vec::new(),
+            };
+
+            assert_eq!(
+                database.count_fresh_ids(),
+                database.count_fresh_ids_naive(),
+                "{:?}",
+                database.ranges
+            );
+        }
+    }
+
+    #[test]
+    fn parse_never_panics() {
+        let mut rng = Rng::new(5);
+        for _ in 0..10_000 {
+            let input = rng.text(&["3", "14", "-", "\n", "\n\n", "x", "é", " "], 16);
+            let _ = <Database as Solution>::parse(&input);
+        }
+    }
+
+    #[test]
+    fn error_location() {
+        let error = Database::from_str("3-5\n10~14\n\n1").err().unwrap();
+        assert_eq!(error.to_string(), "line 2, column 1: invalid range '10~14'");
+
+        let error = Database::from_str("3-5\n\n1\n 1x").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 2: invalid ingredient id '1x'"
+        );
+    }
+
     #[test]
     fn solution_1() {
         let database = Database::from_str(EXAMPLE).unwrap();
@@ -1,4 +1,6 @@
-use std::{num::ParseIntError, str::FromStr};
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
+
+use aoc_common::{Example, Rng, Solution, Span, Spanned};
 
 type Number = u64;
 
@@ -6,28 +8,44 @@ type Number = u64;
 pub enum ParseError {
     InvalidInstruction(String),
     EmptyInput,
+    MissingInstruction,
+    MissingNumber,
+    ExtraNumber,
     ParseInt(ParseIntError),
 }
 
-impl From<ParseIntError> for ParseError {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseInt(value)
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidInstruction(_) => write!(f, "invalid instruction"),
+            Self::EmptyInput => write!(f, "input is empty"),
+            Self::MissingInstruction => write!(f, "no instruction for this problem"),
+            Self::MissingNumber => write!(f, "missing a number, expected one per instruction"),
+            Self::ExtraNumber => write!(f, "more numbers than instructions"),
+            Self::ParseInt(_) => write!(f, "invalid number"),
+        }
     }
 }
 
+impl std::error::Error for ParseError {}
+
 pub enum Instruction {
     Add,
     Multiply,
 }
 
 impl FromStr for Instruction {
-    type Err = ParseError;
+    type Err = Spanned<ParseError>;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         match value {
             "*" => Ok(Self::Multiply),
             "+" => Ok(Self::Add),
-            invalid => Err(ParseError::InvalidInstruction(invalid.to_string())),
+            invalid => Err(Spanned::locate(
+                ParseError::InvalidInstruction(invalid.to_string()),
+                value,
+                invalid,
+            )),
         }
     }
 }
@@ -41,6 +59,12 @@ impl Instruction {
     }
 }
 
+// The character in a column of a line, where lines that are shorter than the first are
+// padded with spaces
+fn cell(line: &str, col: usize) -> &str {
+    line.get(col..col + 1).unwrap_or(" ")
+}
+
 pub struct Worksheet {
     vertical_numbers: Vec<Vec<Number>>,
     horizontal_numbers: Vec<Vec<Number>>,
@@ -48,26 +72,42 @@ pub struct Worksheet {
 }
 
 impl FromStr for Worksheet {
-    type Err = ParseError;
+    type Err = Spanned<ParseError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines().collect::<Vec<_>>();
 
         let instructions = lines
             .pop()
-            .ok_or(ParseError::EmptyInput)?
+            .ok_or_else(|| Spanned::locate(ParseError::EmptyInput, s, s))?
             .split_ascii_whitespace()
-            .map(Instruction::from_str)
-            .collect::<Result<Vec<_>, ParseError>>()?;
+            .map(|instruction| {
+                Instruction::from_str(instruction).map_err(|error| error.within(s, instruction))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         let horizontal_numbers = lines
             .iter()
             .map(|line| {
-                line.split_ascii_whitespace()
-                    .map(|num| num.parse::<Number>())
-                    .collect::<Result<Vec<_>, ParseIntError>>()
+                // Every problem needs a number on every line
+                let numbers = line.split_ascii_whitespace().collect::<Vec<_>>();
+                if let Some(extra) = numbers.get(instructions.len()) {
+                    return Err(Spanned::locate(ParseError::ExtraNumber, s, extra));
+                }
+                if numbers.len() < instructions.len() {
+                    let end = &line[line.len()..];
+                    return Err(Spanned::locate(ParseError::MissingNumber, s, end));
+                }
+
+                numbers
+                    .into_iter()
+                    .map(|num| {
+                        num.parse::<Number>()
+                            .map_err(|error| Spanned::locate(ParseError::ParseInt(error), s, num))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
             })
-            .collect::<Result<Vec<Vec<_>>, ParseIntError>>()?;
+            .collect::<Result<Vec<Vec<_>>, _>>()?;
 
         let columns = lines.first().map(|line| line.len()).unwrap_or(0);
 
@@ -78,7 +118,7 @@ impl FromStr for Worksheet {
 
             // Push each number from the column in reversed order
             for line in lines.iter() {
-                number.push_str(&line[col..col + 1])
+                number.push_str(cell(line, col))
             }
 
             // Trim the string
@@ -88,11 +128,23 @@ impl FromStr for Worksheet {
                 // If it's empty, increment the instruction, and continue
                 instruction_idx += 1;
             } else {
+                // Vertical numbers are read top to bottom, so point at their first digit
+                let span = || {
+                    let line = lines
+                        .iter()
+                        .position(|line| cell(line, col) != " ")
+                        .unwrap_or(0);
+                    Span::new(line + 1, col + 1, trimmed)
+                };
+
                 // Parse and push the string
-                match trimmed.parse() {
-                    Ok(n) => vertical_numbers.get_mut(instruction_idx).unwrap().push(n),
-                    Err(error) => return Err(error),
-                }
+                let n = trimmed
+                    .parse()
+                    .map_err(|error| Spanned::new(ParseError::ParseInt(error), span()))?;
+                vertical_numbers
+                    .get_mut(instruction_idx)
+                    .ok_or_else(|| Spanned::new(ParseError::MissingInstruction, span()))?
+                    .push(n);
             }
 
             Ok(())
@@ -143,15 +195,114 @@ impl Worksheet {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for Worksheet {
+    type Error = Spanned<ParseError>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        name: "EXAMPLE",
+        input: EXAMPLE,
+        part1: Some("4277556"),
+        part2: Some("3263827"),
+    }];
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Self::from_str(input)
+    }
+
+    // `size` problems of four numbers each. The numbers of a problem are aligned to
+    // either side, which changes how they read vertically.
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        const ROWS: usize = 4;
 
-    const EXAMPLE: &str = r#"123 328  51 64 
+        let mut lines = vec![String::new(); ROWS + 1];
+        for problem in 0..size {
+            if problem > 0 {
+                lines.iter_mut().for_each(|line| line.push(' '));
+            }
+
+            // Ordered by length, so the digits of a vertical number don't have gaps
+            let mut numbers = (0..ROWS)
+                .map(|_| rng.range(1..=999).to_string())
+                .collect::<Vec<_>>();
+            numbers.sort_by_key(String::len);
+            if rng.bool() {
+                numbers.reverse();
+            }
+            let width = numbers.iter().map(String::len).max().unwrap_or(1);
+            let left = rng.bool();
+            for (line, number) in lines.iter_mut().zip(&numbers) {
+                if left {
+                    line.push_str(&format!("{number:<width$}"));
+                } else {
+                    line.push_str(&format!("{number:>width$}"));
+                }
+            }
+
+            let instruction = if rng.bool() { '+' } else { '*' };
+            lines[ROWS].push_str(&format!("{instruction:<width$}"));
+        }
+
+        Some(lines.join("\n"))
+    }
+
+    fn part1(&self) -> impl Display {
+        self.solve_horizontal_problems_and_sum()
+    }
+
+    fn part2(&self) -> impl Display {
+        self.solve_vertical_problems_and_sum()
+    }
+}
+
+/// The example from the puzzle description
+pub const EXAMPLE: &str = r#"123 328  51 64 
  45 64  387 23 
   6 98  215 314
 *   +   *   +  "#;
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_never_panics() {
+        let mut rng = Rng::new(6);
+        for _ in 0..10_000 {
+            let input = rng.text(&["1", "23", " ", "  ", "\n", "*", "+", "/", "é", "x"], 16);
+            let _ = <Worksheet as Solution>::parse(&input);
+        }
+    }
+
+    #[test]
+    fn error_location() {
+        let error = Worksheet::from_str("1 2\n3 4\n+ /").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 3: invalid instruction '/'"
+        );
+
+        let error = Worksheet::from_str("1 2\n3 x\n+ *").err().unwrap();
+        assert_eq!(error.to_string(), "line 2, column 3: invalid number 'x'");
+
+        let error = Worksheet::from_str("1  2\n3  4\n+ *").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 4: no instruction for this problem '24'"
+        );
+
+        let error = Worksheet::from_str("1 2 3\n4 5\n+ *").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: more numbers than instructions '3'"
+        );
+
+        let error = Worksheet::from_str("1 2\n3\n+ *").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: missing a number, expected one per instruction"
+        );
+    }
+
     #[test]
     fn solution_1() {
         let sheet = Worksheet::from_str(EXAMPLE).unwrap();
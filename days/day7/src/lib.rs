@@ -1,20 +1,33 @@
 use std::{
     collections::{HashMap, HashSet},
+    fmt::Display,
     str::FromStr,
 };
 
+use aoc_common::{Example, Rng, Solution, Span, Spanned};
+
 #[derive(Debug)]
 pub enum ParseError {
     NoInput,
 }
 
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoInput => write!(f, "no beam input 'S' on the first line"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 pub struct TachyonManifold {
     input: usize,
     splitters: Vec<usize>,
 }
 
 impl FromStr for TachyonManifold {
-    type Err = ParseError;
+    type Err = Spanned<ParseError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
@@ -25,7 +38,7 @@ impl FromStr for TachyonManifold {
                     .find(|(_, c)| *c == 'S')
                     .map(|(column, _)| column)
             })
-            .ok_or(ParseError::NoInput)?;
+            .ok_or_else(|| Spanned::new(ParseError::NoInput, Span::new(1, 1, "")))?;
 
         let splitters = lines
             .flat_map(|line| {
@@ -79,11 +92,65 @@ impl TachyonManifold {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for TachyonManifold {
+    type Error = Spanned<ParseError>;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            name: "EXAMPLE",
+            input: EXAMPLE,
+            part1: Some("21"),
+            part2: Some("40"),
+        },
+        Example {
+            name: "CASE",
+            input: CASE,
+            part1: Some("6"),
+            part2: Some("8"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Self::from_str(input)
+    }
+
+    // A `size` by `size` manifold with splitters on every other line. They get sparser
+    // as the manifold grows, so the number of timelines still fits in a `usize`.
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let size = size.max(3);
+        let per_mille = (40_000 / size as u64).min(500);
+
+        let mut lines = vec![format!(
+            "{}S{}",
+            ".".repeat(size / 2),
+            ".".repeat(size - size / 2 - 1)
+        )];
+        for row in 1..size {
+            let line = (0..size)
+                .map(|column| {
+                    let splitter = row % 2 == 0
+                        && (1..size - 1).contains(&column)
+                        && rng.range(0..=999) < per_mille;
+                    if splitter { '^' } else { '.' }
+                })
+                .collect();
+            lines.push(line);
+        }
+
+        Some(lines.join("\n"))
+    }
+
+    fn part1(&self) -> impl Display {
+        self.calculate_beam_splits()
+    }
+
+    fn part2(&self) -> impl Display {
+        self.calculate_beam_timelines()
+    }
+}
 
-    const EXAMPLE: &str = r#".......S.......
+/// The example from the puzzle description
+pub const EXAMPLE: &str = r#".......S.......
 ...............
 .......^.......
 ...............
@@ -100,7 +167,8 @@ mod tests {
 .^.^.^.^.^...^.
 ..............."#;
 
-    const CASE: &str = r#"...S...
+/// A smaller manifold whose timelines are easy to count by hand
+pub const CASE: &str = r#"...S...
 .......
 ...^...
 .......
@@ -108,6 +176,28 @@ mod tests {
 .......
 .^.^.^."#;
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_never_panics() {
+        let mut rng = Rng::new(7);
+        for _ in 0..10_000 {
+            let input = rng.text(&[".", "S", "^", "\n", "é", "x"], 16);
+            let _ = <TachyonManifold as Solution>::parse(&input);
+        }
+    }
+
+    #[test]
+    fn error_location() {
+        let error = TachyonManifold::from_str("...\n.S.").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: no beam input 'S' on the first line"
+        );
+    }
+
     #[test]
     fn case() {
         let manifold = TachyonManifold::from_str(CASE).unwrap();
@@ -1,121 +1,80 @@
-use std::{collections::BTreeMap, num::ParseIntError, str::FromStr};
+use std::{collections::BTreeMap, fmt::Display, num::ParseIntError, str::FromStr};
+
+use aoc_common::{DisjointSet, Example, Point3, Rng, Solution, Spanned};
 
 #[derive(Debug)]
 pub enum ParseError {
     ParseInt(ParseIntError),
     EmptyPosition,
+    TooFar,
 }
 
-impl From<ParseIntError> for ParseError {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseInt(value)
-    }
-}
-
-type Number = isize;
-
-struct UnionFind {
-    parent: Vec<usize>,
-    rank: Vec<usize>,
-    size: Vec<usize>,
-}
-
-impl UnionFind {
-    fn new(n: usize) -> Self {
-        Self {
-            parent: (0..n).collect(),
-            rank: vec![0; n],
-            size: vec![1; n],
-        }
-    }
-
-    #[inline]
-    fn find(&mut self, x: usize) -> usize {
-        if self.parent[x] != x {
-            self.parent[x] = self.find(self.parent[x]);
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseInt(_) => write!(f, "invalid coordinate"),
+            Self::EmptyPosition => write!(f, "missing a coordinate, expected 'x,y,z'"),
+            Self::TooFar => write!(f, "too far from the other junction boxes"),
         }
-        self.parent[x]
     }
+}
 
-    fn union(&mut self, x: usize, y: usize) -> bool {
-        let px = self.find(x);
-        let py = self.find(y);
-
-        if px == py {
-            return false; // Already in same set
-        }
+impl std::error::Error for ParseError {}
 
-        // Union by rank
-        if self.rank[px] < self.rank[py] {
-            self.parent[px] = py;
-            self.size[py] += self.size[px];
-        } else if self.rank[px] > self.rank[py] {
-            self.parent[py] = px;
-            self.size[px] += self.size[py];
-        } else {
-            self.parent[py] = px;
-            self.size[px] += self.size[py];
-            self.rank[px] += 1;
-        }
-        true
-    }
-
-}
+type Number = isize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Position {
-    x: Number,
-    y: Number,
-    z: Number,
-}
+const CONNECTIONS: usize = 1000;
+// The example only makes the 10 shortest connections
+const EXAMPLE_CONNECTIONS: usize = 10;
 
-impl std::fmt::Display for Position {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{},{},{}", self.x, self.y, self.z)
-    }
-}
+type Position = Point3<Number>;
 
-impl FromStr for Position {
-    type Err = ParseError;
+fn parse_position(s: &str) -> Result<Position, Spanned<ParseError>> {
+    let mut iter = s.split(',');
+    let mut next = || {
+        let n = iter
+            .next()
+            .ok_or_else(|| Spanned::locate(ParseError::EmptyPosition, s, &s[s.len()..]))?;
+        n.parse()
+            .map_err(|error| Spanned::locate(ParseError::ParseInt(error), s, n))
+    };
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split(',').map(|n| n.parse());
-        Ok(Self {
-            x: iter.next().ok_or(ParseError::EmptyPosition)??,
-            y: iter.next().ok_or(ParseError::EmptyPosition)??,
-            z: iter.next().ok_or(ParseError::EmptyPosition)??,
-        })
-    }
+    Ok(Point3::new(next()?, next()?, next()?))
 }
 
+// `Point3::squared_distance`, but `None` if the distance doesn't fit in a `Number`
 #[inline(always)]
-fn squared_distance(p1: &Position, p2: &Position) -> Number {
-    let dx = p2.x - p1.x;
-    let dy = p2.y - p1.y;
-    let dz = p2.z - p1.z;
-    dx * dx + dy * dy + dz * dz
+fn squared_distance(p1: &Position, p2: &Position) -> Option<Number> {
+    let squared = |a: Number, b: Number| b.checked_sub(a).and_then(|d| d.checked_mul(d));
+    squared(p1.x, p2.x)?
+        .checked_add(squared(p1.y, p2.y)?)?
+        .checked_add(squared(p1.z, p2.z)?)
 }
 
 pub struct JunctionBoxes {
     // Distance -> index pairs (instead of full Position structs)
     distances: BTreeMap<Number, Vec<(usize, usize)>>,
     boxes: Vec<Position>,
+    // Number of connections to make for part 1
+    connections: usize,
 }
 
 impl FromStr for JunctionBoxes {
-    type Err = ParseError;
+    type Err = Spanned<ParseError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let boxes = s
-            .lines()
-            .map(Position::from_str)
+        let lines = s.lines().collect::<Vec<_>>();
+        let boxes = lines
+            .iter()
+            .map(|line| parse_position(line).map_err(|error| error.within(s, line)))
             .collect::<Result<Vec<_>, _>>()?;
 
         let mut distances = BTreeMap::new();
 
         for i in 0..boxes.len() {
             for j in (i + 1)..boxes.len() {
-                let distance = squared_distance(&boxes[i], &boxes[j]);
+                let distance = squared_distance(&boxes[i], &boxes[j])
+                    .ok_or_else(|| Spanned::locate(ParseError::TooFar, s, lines[j]))?;
                 distances
                     .entry(distance)
                     .or_insert_with(Vec::new)
@@ -123,13 +82,17 @@ impl FromStr for JunctionBoxes {
             }
         }
 
-        Ok(Self { distances, boxes })
+        Ok(Self {
+            distances,
+            boxes,
+            connections: CONNECTIONS,
+        })
     }
 }
 
 impl JunctionBoxes {
     pub fn find_connections(&self, iterations: usize) -> usize {
-        let mut uf = UnionFind::new(self.boxes.len());
+        let mut uf = DisjointSet::new(self.boxes.len());
         let mut counter = 0;
 
         'outer: for pairs in self.distances.values() {
@@ -143,25 +106,17 @@ impl JunctionBoxes {
             }
         }
 
-        // Find all unique roots and their component sizes
-        let mut sizes: Vec<usize> = (0..self.boxes.len())
-            .filter_map(|i| {
-                if uf.find(i) == i {
-                    Some(uf.size[i])
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let mut sizes = uf.set_sizes();
 
         sizes.sort_unstable();
         sizes.reverse();
 
-        sizes[0] * sizes[1] * sizes[2]
+        // Small inputs can end up with fewer than three circuits
+        sizes.iter().take(3).product()
     }
 
     pub fn find_last_distance(&self) -> isize {
-        let mut uf = UnionFind::new(self.boxes.len());
+        let mut uf = DisjointSet::new(self.boxes.len());
         let mut last_pair = (0, 0);
 
         for pairs in self.distances.values() {
@@ -176,11 +131,51 @@ impl JunctionBoxes {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for JunctionBoxes {
+    type Error = Spanned<ParseError>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        name: "EXAMPLE",
+        input: EXAMPLE,
+        part1: Some("40"),
+        part2: Some("25272"),
+    }];
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Self::from_str(input)
+    }
+
+    fn parse_example(input: &str) -> Result<Self, Self::Error> {
+        Ok(Self {
+            connections: EXAMPLE_CONNECTIONS,
+            ..Self::from_str(input)?
+        })
+    }
+
+    // `size` junction boxes spread through a cube, like the puzzle input, and at least
+    // two so there is something to connect
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let boxes = (0..size.max(2))
+            .map(|_| {
+                let mut coordinate = || rng.range(0..=99_999);
+                format!("{},{},{}", coordinate(), coordinate(), coordinate())
+            })
+            .collect::<Vec<_>>();
+
+        Some(boxes.join("\n"))
+    }
 
-    const EXAMPLE: &str = r#"162,817,812
+    fn part1(&self) -> impl Display {
+        self.find_connections(self.connections)
+    }
+
+    fn part2(&self) -> impl Display {
+        self.find_last_distance()
+    }
+}
+
+/// The example from the puzzle description
+pub const EXAMPLE: &str = r#"162,817,812
 57,618,57
 906,360,560
 592,479,940
@@ -202,10 +197,53 @@ mod tests {
 425,690,689
 "#;
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_never_panics() {
+        let mut rng = Rng::new(8);
+        for _ in 0..10_000 {
+            let input = rng.text(
+                &["1", "-2", "817", "5000000000", ",", "\n", "x", "é", " "],
+                16,
+            );
+            let _ = <JunctionBoxes as Solution>::parse(&input);
+        }
+    }
+
+    #[test]
+    fn error_location() {
+        let error = JunctionBoxes::from_str("162,817,812\n57,618")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 7: missing a coordinate, expected 'x,y,z'"
+        );
+
+        let error = JunctionBoxes::from_str("162,817,812\n57,-x,57")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: invalid coordinate '-x'"
+        );
+
+        let error = JunctionBoxes::from_str("1,2,3\n5000000000,2,3")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: too far from the other junction boxes '5000000000,2,3'"
+        );
+    }
+
     #[test]
     fn solution_1() {
         let boxes = JunctionBoxes::from_str(EXAMPLE).unwrap();
-        assert_eq!(boxes.find_connections(10), 40)
+        assert_eq!(boxes.find_connections(EXAMPLE_CONNECTIONS), 40)
     }
 
     #[test]
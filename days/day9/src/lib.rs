@@ -1,91 +1,70 @@
-use std::{num::ParseIntError, str::FromStr};
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
+
+use aoc_common::{Example, Point2, Rect, Rng, Solution, Spanned};
 
 #[derive(Debug)]
 pub enum ParseError {
     ParseInt(ParseIntError),
     InvalidPosition,
+    EmptyInput,
 }
 
-impl From<ParseIntError> for ParseError {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseInt(value)
-    }
-}
-
-type Number = usize;
-
-struct Rect {
-    min_x: Number,
-    max_x: Number,
-    min_y: Number,
-    max_y: Number,
-}
-
-impl Rect {
-    #[inline(always)]
-    fn new(p1: Tile, p2: Tile) -> Self {
-        Self {
-            min_x: p1.x.min(p2.x),
-            max_x: p1.x.max(p2.x),
-            min_y: p1.y.min(p2.y),
-            max_y: p1.y.max(p2.y),
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseInt(_) => write!(f, "invalid coordinate"),
+            Self::InvalidPosition => write!(f, "missing a coordinate, expected 'x,y'"),
+            Self::EmptyInput => write!(f, "input is empty"),
         }
     }
-
-    #[inline(always)]
-    fn intersects_with(&self, other: &Self) -> bool {
-        self.min_x < other.max_x
-            && self.max_x > other.min_x
-            && self.min_y < other.max_y
-            && self.max_y > other.min_y
-    }
-
-    #[inline(always)]
-    fn area(&self) -> Number {
-        (self.min_x.abs_diff(self.max_x) + 1) * (self.min_y.abs_diff(self.max_y) + 1)
-    }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct Tile {
-    x: Number,
-    y: Number,
-}
+impl std::error::Error for ParseError {}
 
-impl FromStr for Tile {
-    type Err = ParseError;
+type Number = usize;
 
-    #[inline]
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut numbers = s.split(",");
-        let x = numbers.next().ok_or(ParseError::InvalidPosition)?.parse()?;
-        let y = numbers.next().ok_or(ParseError::InvalidPosition)?.parse()?;
-        Ok(Self { x, y })
-    }
+type Tile = Point2<Number>;
+
+fn parse_tile(s: &str) -> Result<Tile, Spanned<ParseError>> {
+    let mut numbers = s.split(",");
+    let mut next = || {
+        let n = numbers
+            .next()
+            .ok_or_else(|| Spanned::locate(ParseError::InvalidPosition, s, &s[s.len()..]))?;
+        n.parse()
+            .map_err(|error| Spanned::locate(ParseError::ParseInt(error), s, n))
+    };
+
+    let x = next()?;
+    let y = next()?;
+    Ok(Point2::new(x, y))
 }
 
 pub struct Floor {
     tiles: Vec<Tile>,
-    edges: Vec<Rect>,
+    edges: Vec<Rect<Number>>,
 }
 
 impl FromStr for Floor {
-    type Err = ParseError;
+    type Err = Spanned<ParseError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let tiles = s
             .lines()
-            .map(Tile::from_str)
+            .map(|line| parse_tile(line).map_err(|error| error.within(s, line)))
             .collect::<Result<Vec<_>, _>>()?;
 
-        let mut edges: Vec<Rect> = tiles.windows(2).map(|t| Rect::new(t[0], t[1])).collect();
+        let mut edges: Vec<_> = tiles
+            .windows(2)
+            .map(|t| Rect::from_corners(t[0], t[1]))
+            .collect();
 
-        edges.push(
-            tiles
-                .first()
-                .map(|t1| Rect::new(*t1, *tiles.last().unwrap()))
-                .unwrap(),
-        );
+        // Close the loop from the last tile back to the first
+        let (first, last) = tiles
+            .first()
+            .zip(tiles.last())
+            .ok_or_else(|| Spanned::locate(ParseError::EmptyInput, s, s))?;
+        edges.push(Rect::from_corners(*first, *last));
 
         Ok(Self { tiles, edges })
     }
@@ -99,7 +78,7 @@ impl Floor {
             for j in i..self.tiles.len() {
                 let r1 = self.tiles[i];
                 let r2 = self.tiles[j];
-                let rect = Rect::new(r1, r2);
+                let rect = Rect::from_corners(r1, r2);
                 let area = rect.area();
 
                 if check_intersect && check_intersections(&self.edges, &rect) {
@@ -115,9 +94,9 @@ impl Floor {
 }
 
 #[inline]
-fn check_intersections(edges: &Vec<Rect>, rect: &Rect) -> bool {
+fn check_intersections(edges: &Vec<Rect<Number>>, rect: &Rect<Number>) -> bool {
     for edge in edges {
-        if rect.intersects_with(edge) {
+        if rect.overlaps_interior(edge) {
             return true;
         }
     }
@@ -125,11 +104,59 @@ fn check_intersections(edges: &Vec<Rect>, rect: &Rect) -> bool {
     false
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for Floor {
+    type Error = Spanned<ParseError>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        name: "EXAMPLE",
+        input: EXAMPLE,
+        part1: Some("50"),
+        part2: Some("24"),
+    }];
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Self::from_str(input)
+    }
 
-    const EXAMPLE: &str = r#"7,1
+    // A staircase of about `size` red tiles: steps of varying height on top of a flat
+    // bottom edge, which keeps the loop rectilinear and free of self-intersections
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        const BOTTOM: u64 = 1;
+
+        let steps = (size / 2).saturating_sub(1).max(1);
+        let mut x = rng.range(1..=1000);
+        let mut tiles = vec![(x, BOTTOM)];
+        let mut previous = BOTTOM;
+        for _ in 0..steps {
+            let mut height = rng.range(BOTTOM + 1..=99_999);
+            if height == previous {
+                height += 1;
+            }
+            tiles.push((x, height));
+            x += rng.range(1..=1000);
+            tiles.push((x, height));
+            previous = height;
+        }
+        tiles.push((x, BOTTOM));
+
+        let tiles = tiles
+            .into_iter()
+            .map(|(x, y)| format!("{x},{y}"))
+            .collect::<Vec<_>>();
+        Some(tiles.join("\n"))
+    }
+
+    fn part1(&self) -> impl Display {
+        self.find_largest_area(false)
+    }
+
+    fn part2(&self) -> impl Display {
+        self.find_largest_area(true)
+    }
+}
+
+/// The example from the puzzle description
+pub const EXAMPLE: &str = r#"7,1
 11,1
 11,7
 9,7
@@ -139,6 +166,28 @@ mod tests {
 7,3
 "#;
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_never_panics() {
+        let mut rng = Rng::new(9);
+        for _ in 0..10_000 {
+            let input = rng.text(&["7", "11", ",", "\n", "x", "é", " ", ";"], 16);
+            let _ = <Floor as Solution>::parse(&input);
+        }
+    }
+
+    #[test]
+    fn error_location() {
+        let error = Floor::from_str("7,1\n11;1").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: invalid coordinate '11;1'"
+        );
+    }
+
     #[test]
     fn solution_1() {
         let floor = Floor::from_str(EXAMPLE).unwrap();
@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc_common::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// Any input has to parse or fail with a `ParseError`, never panic
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = <day1::DialInstructions as Solution>::parse(input);
+    }
+});